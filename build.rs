@@ -0,0 +1,112 @@
+//! Generates the list of day modules that lets the main binary run solutions in-process.
+//! See `template::registry` for how the generated `solutions.rs` is used.
+//!
+//! Also generates the example test of every day, which is ignored if the day has no manifest of examples.
+use std::{collections::BTreeSet, env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    // days of the configured year are named `DD.rs`, days of other years `YYYY-DD.rs`.
+    let mut days: Vec<String> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let stem = path.file_stem()?.to_str()?;
+            let is_number = |s: &str, len| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+            let is_day = path.extension()? == "rs"
+                && match stem.split_once('-') {
+                    Some((year, day)) => is_number(year, 4) && is_number(day, 2),
+                    None => is_number(stem, 2),
+                };
+            is_day.then(|| stem.to_string())
+        })
+        .collect();
+
+    days.sort();
+
+    write_example_tests(Path::new(&manifest_dir), &days);
+
+    // every solution installs its own global allocator for dhat, so they cannot share a binary.
+    if env::var_os("CARGO_FEATURE_DHAT_HEAP").is_some() {
+        days.clear();
+    }
+
+    let mut code = String::new();
+
+    let module = |day: &str| format!("day_{}", day.replace('-', "_"));
+
+    for day in &days {
+        let path = bin_dir.join(format!("{day}.rs"));
+        code.push_str(&format!(
+            "#[cfg(not(test))]\n#[allow(dead_code)]\n#[path = {:?}]\nmod {};\n\n",
+            path.display().to_string(),
+            module(day)
+        ));
+    }
+
+    code.push_str("#[cfg(not(test))]\n");
+    code.push_str("pub static SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n");
+    for day in &days {
+        code.push_str(&format!("    {}::SOLUTION,\n", module(day)));
+    }
+    code.push_str("];\n\n");
+
+    // the day modules are tested as part of their own binaries.
+    code.push_str("#[cfg(test)]\n");
+    code.push_str("pub static SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), code).unwrap();
+}
+
+/// Writes `examples/{day}.rs` for the `solution!` macro to include into the tests of a day.
+/// Days of the configured year are named `DD`, days of other years `YYYY-DD`.
+fn write_example_tests(manifest_dir: &Path, days: &[String]) {
+    println!("cargo:rerun-if-env-changed=AOC_YEAR");
+
+    let configured_year = env::var("AOC_YEAR").ok();
+    let out_dir = Path::new(&env::var("OUT_DIR").unwrap()).join("examples");
+    fs::create_dir_all(&out_dir).unwrap();
+
+    let mut examples_dirs = BTreeSet::new();
+
+    for day in days {
+        let (year, day_number) = match day.split_once('-') {
+            Some((year, day_number)) => (Some(year), day_number),
+            None => (configured_year.as_deref(), day.as_str()),
+        };
+
+        let manifest = year.map(|year| format!("data/{year}/examples/{day_number}.json"));
+
+        if let Some(dir) = manifest
+            .as_ref()
+            .and_then(|manifest| Path::new(manifest).parent())
+            .map(|dir| manifest_dir.join(dir))
+            .filter(|dir| dir.is_dir())
+        {
+            examples_dirs.insert(dir);
+        }
+
+        let ignore = match manifest {
+            Some(manifest) if manifest_dir.join(&manifest).is_file() => String::new(),
+            Some(manifest) => format!("#[ignore = \"no example manifest at {manifest}\"]\n"),
+            None => "#[ignore = \"no example manifest, `AOC_YEAR` is not set\"]\n".into(),
+        };
+
+        let code = format!("#[test]\n{ignore}fn test_examples() {{\n    check_examples();\n}}\n");
+
+        // only touch files that change, so days are not rebuilt for nothing.
+        let path = out_dir.join(format!("{day}.rs"));
+        if fs::read_to_string(&path).ok().as_deref() != Some(code.as_str()) {
+            fs::write(path, code).unwrap();
+        }
+    }
+
+    for dir in examples_dirs {
+        println!("cargo:rerun-if-changed={}", dir.display());
+    }
+}
@@ -50,8 +50,8 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(n_safe)
 }
 
-fn vec_without(vec: &Vec<u32>, i: usize) -> Vec<u32> {
-    let mut new_vec = vec.clone();
+fn vec_without(vec: &[u32], i: usize) -> Vec<u32> {
+    let mut new_vec = vec.to_vec();
     _ = new_vec.remove(i);
     new_vec
 }
@@ -116,13 +116,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(7));
     }
 }
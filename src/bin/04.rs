@@ -73,7 +73,7 @@ fn pos_to_indices(pos: (i32, i32)) -> (usize, usize) {
 fn continues_word(
     char_mat: &DMatrix<char>,
     pos: (usize, usize),
-    word: &String,
+    word: &str,
     direction: &Direction,
 ) -> bool {
     let mut word_iter = word.chars();
@@ -213,13 +213,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(18));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(9));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse = parse_input);
 
 use std::collections::{HashMap, HashSet};
 
@@ -18,7 +18,9 @@ fn update_hashmap(hash_map: &mut HashMap<String, HashSet<String>>, key: String,
     hash_map.get_mut(&key).unwrap().insert(behind.clone());
 }
 
-fn parse_input(input: &str) -> (HashMap<String, HashSet<String>>, Vec<Vec<&str>>) {
+type Input<'a> = (HashMap<String, HashSet<String>>, Vec<Vec<&'a str>>);
+
+fn parse_input(input: &str) -> Input<'_> {
     let mut rule_map: HashMap<String, HashSet<String>> = HashMap::new();
     let mut print_vec: Vec<Vec<&str>> = Vec::new();
 
@@ -45,7 +47,7 @@ fn parse_input(input: &str) -> (HashMap<String, HashSet<String>>, Vec<Vec<&str>>
     (rule_map, print_vec)
 }
 
-fn get_middle_job_value(job: &Vec<&str>) -> u32 {
+fn get_middle_job_value(job: &[&str]) -> u32 {
     let middle = (job.len() as f32 / 2_f32).ceil() - 1_f32;
     let middle_str = job[middle as usize];
 
@@ -84,14 +86,12 @@ fn job_conforms(job: &Vec<&str>, rule_map: &HashMap<String, HashSet<String>>) ->
     true
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (rules, print_queue) = parse_input(input);
-
+pub fn part_one((rules, print_queue): &Input) -> Option<u32> {
     let mut print_sum: u32 = 0;
 
     for job in print_queue {
-        if job_conforms(&job, &rules) {
-            print_sum += get_middle_job_value(&job);
+        if job_conforms(job, rules) {
+            print_sum += get_middle_job_value(job);
         }
     }
 
@@ -124,19 +124,17 @@ fn make_conforming<'a>(
         order_vec.push((*page, rule_set.intersection(&job_set).count() as u32));
     }
 
-    order_vec.sort_by(|a, b| a.1.cmp(&b.1));
+    order_vec.sort_by_key(|a| a.1);
 
     order_vec.iter().map(|x| x.0).collect()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (rules, print_queue) = parse_input(input);
-
+pub fn part_two((rules, print_queue): &Input) -> Option<u32> {
     let mut print_sum: u32 = 0;
 
     for job in print_queue {
-        if !job_conforms(&job, &rules) {
-            let conforming_job = make_conforming(&job, &rules);
+        if !job_conforms(job, rules) {
+            let conforming_job = make_conforming(job, rules);
             print_sum += get_middle_job_value(&conforming_job);
         }
     }
@@ -150,13 +148,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = part_one(&parse_input(&input));
         assert_eq!(result, Some(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = part_two(&parse_input(&input));
         assert_eq!(result, Some(123));
     }
 }
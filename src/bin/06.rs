@@ -1,10 +1,12 @@
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, parse = parse_input);
 
 use std::collections::HashSet;
 
 type IterType = std::iter::Cycle<std::vec::IntoIter<(i32, i32)>>;
 
-fn parse_input(input: &str) -> ((usize, usize), Vec<Vec<char>>) {
+type Input = ((usize, usize), Vec<Vec<char>>);
+
+fn parse_input(input: &str) -> Input {
     let mut output = Vec::new();
     let mut init_pos = (0, 0);
     let init_pos_char = '^';
@@ -133,7 +135,7 @@ impl Guard<'_> {
     }
 }
 
-fn build_guard(init_pos: (usize, usize), map: &Vec<Vec<char>>) -> Guard {
+fn build_guard(init_pos: (usize, usize), map: &Vec<Vec<char>>) -> Guard<'_> {
     let visited_set = [init_pos].iter().copied().collect();
     let dimensions = (map.len(), map[0].len());
     let directions = vec![(-1, 0), (0, 1), (1, 0), (0, -1)];
@@ -151,10 +153,8 @@ fn build_guard(init_pos: (usize, usize), map: &Vec<Vec<char>>) -> Guard {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (init_pos, map) = parse_input(input);
-
-    let mut guard = build_guard(init_pos, &map);
+pub fn part_one((init_pos, map): &Input) -> Option<u32> {
+    let mut guard = build_guard(*init_pos, map);
 
     loop {
         // Assuming guard can't walk in circles.
@@ -166,10 +166,8 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(guard.visited_positions.len() as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (init_pos, map) = parse_input(input);
-
-    let mut guard = build_guard(init_pos, &map);
+pub fn part_two((init_pos, map): &Input) -> Option<u32> {
+    let mut guard = build_guard(*init_pos, map);
 
     Some(guard.count_new_boxes())
 }
@@ -180,13 +178,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = part_one(&parse_input(&input));
         assert_eq!(result, Some(41));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = part_two(&parse_input(&input));
         assert_eq!(result, Some(6));
     }
 }
@@ -1,40 +1,68 @@
 advent_of_code::solution!(7);
 
+use std::error::Error;
+use std::fmt::Display;
+
+use advent_of_code::parse::{self, ParseError};
 use itertools::{repeat_n, Itertools};
 
-fn add(a: u64, b: u64) -> u64 {
-    a.checked_add(b).unwrap()
+type Operator = fn(u64, u64) -> Option<u64>;
+
+#[derive(Debug, PartialEq)]
+pub enum CalibrationError {
+    Parse(ParseError),
+    /// The sum of the correct calibrations does not fit a `u64`.
+    SumOverflow,
 }
 
-fn mul(a: u64, b: u64) -> u64 {
-    a.checked_mul(b).unwrap()
+impl Display for CalibrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CalibrationError::Parse(_) => f.write_str("invalid equation"),
+            CalibrationError::SumOverflow => {
+                f.write_str("the sum of the calibrations overflows a u64")
+            }
+        }
+    }
 }
 
-fn concat(a: u64, b: u64) -> u64 {
-    let b_magnitude = b.ilog10();
-    let b_pow = 10u64.checked_pow(b_magnitude + 1).unwrap();
-    a.checked_mul(b_pow).unwrap().checked_add(b).unwrap()
+impl Error for CalibrationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CalibrationError::Parse(error) => Some(error),
+            CalibrationError::SumOverflow => None,
+        }
+    }
 }
 
-const OPERATORS: [fn(u64, u64) -> u64; 2] = [add, mul];
+impl From<ParseError> for CalibrationError {
+    fn from(value: ParseError) -> Self {
+        CalibrationError::Parse(value)
+    }
+}
 
-fn parse_input(input: &str) -> Vec<(u64, Vec<u64>)> {
-    let mut output = Vec::new();
+fn add(a: u64, b: u64) -> Option<u64> {
+    a.checked_add(b)
+}
 
-    for line in input.lines() {
-        let (cal_result, vals_str) = line.split_once(": ").unwrap();
-        let cal_values = vals_str.split_whitespace();
+fn mul(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(b)
+}
 
-        output.push((
-            cal_result.parse().unwrap(),
-            cal_values.map(|val_str| val_str.parse().unwrap()).collect(),
-        ))
-    }
+fn concat(a: u64, b: u64) -> Option<u64> {
+    let b_magnitude = b.checked_ilog10().unwrap_or(0);
+    let b_pow = 10u64.checked_pow(b_magnitude + 1)?;
+    a.checked_mul(b_pow)?.checked_add(b)
+}
+
+const OPERATORS: [Operator; 2] = [add, mul];
 
-    output
+fn parse_input(input: &str) -> Result<Vec<(u64, Vec<u64>)>, ParseError> {
+    parse::lines_with(input, parse::key_values)
 }
 
-fn calibration_correct(calibration: &(u64, Vec<u64>), operators: &[fn(u64, u64) -> u64]) -> bool {
+/// Whether some sequence of operators gives the result.
+fn calibration_correct(calibration: &(u64, Vec<u64>), operators: &[Operator]) -> bool {
     let (cal_result, cal_values) = calibration;
     let n_operators = cal_values.len() - 1;
 
@@ -42,11 +70,12 @@ fn calibration_correct(calibration: &(u64, Vec<u64>), operators: &[fn(u64, u64)
         let mut values_iter = cal_values.iter();
         let first_value = values_iter.next().unwrap();
 
+        // the operators never decrease a positive value, so a sequence that overflows cannot give the result.
         let result = values_iter
             .zip(operator_seq)
-            .fold(*first_value, |acc, (val, op)| op(acc, *val));
+            .try_fold(*first_value, |acc, (val, op)| op(acc, *val));
 
-        if result == *cal_result {
+        if result == Some(*cal_result) {
             return true;
         }
     }
@@ -54,33 +83,28 @@ fn calibration_correct(calibration: &(u64, Vec<u64>), operators: &[fn(u64, u64)
     false
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let calibrations = parse_input(input);
+fn sum_correct(input: &str, operators: &[Operator]) -> Result<u64, CalibrationError> {
+    let calibrations = parse_input(input)?;
 
-    let mut sum_tot_cals = 0;
+    let mut sum_tot_cals: u64 = 0;
 
     for calibration in calibrations {
-        if calibration_correct(&calibration, &OPERATORS) {
-            sum_tot_cals += calibration.0;
+        if calibration_correct(&calibration, operators) {
+            sum_tot_cals = sum_tot_cals
+                .checked_add(calibration.0)
+                .ok_or(CalibrationError::SumOverflow)?;
         }
     }
 
-    Some(sum_tot_cals)
+    Ok(sum_tot_cals)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let calibrations = parse_input(input);
-
-    let mut sum_tot_cals = 0;
-    let operators = [OPERATORS[0], OPERATORS[1], concat];
-
-    for calibration in calibrations {
-        if calibration_correct(&calibration, &operators) {
-            sum_tot_cals += calibration.0;
-        }
-    }
+pub fn part_one(input: &str) -> Result<u64, CalibrationError> {
+    sum_correct(input, &OPERATORS)
+}
 
-    Some(sum_tot_cals)
+pub fn part_two(input: &str) -> Result<u64, CalibrationError> {
+    sum_correct(input, &[OPERATORS[0], OPERATORS[1], concat])
 }
 
 #[cfg(test)]
@@ -89,13 +113,25 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(3749));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(11387));
+    }
+
+    #[test]
+    fn test_overflow() {
+        // adding overflows, multiplying gives the result.
+        let input = "18446744073709551615: 18446744073709551615 1\n";
+        assert_eq!(part_one(input), Ok(u64::MAX));
+        assert_eq!(part_two(input), Ok(u64::MAX));
+
+        let input = format!("{input}190: 10 19\n");
+        assert_eq!(part_one(&input), Err(CalibrationError::SumOverflow));
+        assert_eq!(part_two(&input), Err(CalibrationError::SumOverflow));
     }
 }
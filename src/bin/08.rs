@@ -110,14 +110,9 @@ fn resonant_pos_pair_antinodes(
     let mut pos = *pos_pair.0;
     let mut diff_arr = [diff[0], diff[1]];
 
-    loop {
-        match extend_pos_line(&pos, &diff_arr, dims) {
-            Some(new_pos) => {
-                pos = new_pos;
-                antinode_vec.push(pos);
-            }
-            None => break,
-        }
+    while let Some(new_pos) = extend_pos_line(&pos, &diff_arr, dims) {
+        pos = new_pos;
+        antinode_vec.push(pos);
     }
 
     pos = *pos_pair.1;
@@ -126,14 +121,9 @@ fn resonant_pos_pair_antinodes(
         diff[1].checked_neg().unwrap(),
     ];
 
-    loop {
-        match extend_pos_line(&pos, &diff_arr, dims) {
-            Some(new_pos) => {
-                pos = new_pos;
-                antinode_vec.push(pos);
-            }
-            None => break,
-        }
+    while let Some(new_pos) = extend_pos_line(&pos, &diff_arr, dims) {
+        pos = new_pos;
+        antinode_vec.push(pos);
     }
 
     antinode_vec
@@ -162,13 +152,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(14));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(34));
     }
 }
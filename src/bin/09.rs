@@ -34,7 +34,7 @@ fn parse_input(input: &str) -> Vec<Option<usize>> {
     disk_map
 }
 
-fn compress_disk(disk_map: &mut Vec<Option<usize>>) -> u64 {
+fn compress_disk(disk_map: &mut [Option<usize>]) -> u64 {
     let mut i: usize = 0;
     let mut j: usize = disk_map.len() - 1;
     let mut checksum: usize = 0;
@@ -100,13 +100,13 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(checksum)
 }
 
-fn parse_input_contig(
-    input: &str,
-) -> (
+type ContigDisk = (
     Vec<Option<usize>>,
     Vec<(usize, usize)>,
     HashMap<usize, VecDeque<usize>>,
-) {
+);
+
+fn parse_input_contig(input: &str) -> ContigDisk {
     let trim_input = input.trim_end();
     let pos_lens: Vec<usize> = trim_input
         .chars()
@@ -195,7 +195,7 @@ fn insert_new_space(size: usize, pos: usize, free_space: &mut HashMap<usize, Vec
 }
 
 fn compress_disk_contig(
-    disk_map: &mut Vec<Option<usize>>,
+    disk_map: &mut [Option<usize>],
     files: Vec<(usize, usize)>,
     free_space: &mut HashMap<usize, VecDeque<usize>>,
 ) -> u64 {
@@ -276,13 +276,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(1928));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2858));
     }
 }
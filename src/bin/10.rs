@@ -1,142 +1,51 @@
 advent_of_code::solution!(10);
 
-use std::collections::HashSet;
+use advent_of_code::grid::{Grid, Pos};
+use advent_of_code::parse;
+use advent_of_code::search::{bfs, Search};
 
-const DIRECTIONS: [[i32; 2]; 4] = [[-1, 0], [0, -1], [0, 1], [1, 0]];
-
-fn parse_input(input: &str) -> Vec<Vec<u32>> {
-    let mut output = Vec::new();
-
-    for line in input.lines() {
-        output.push(
-            line.chars()
-                .map(|height_char| height_char.to_digit(10).unwrap())
-                .collect::<Vec<u32>>(),
-        );
-    }
-
-    output
+fn parse_input(input: &str) -> Grid<u32> {
+    parse::digit_grid(input).unwrap()
 }
 
-fn count_peaks_from_trailhead(
-    map: &Vec<Vec<u32>>,
-    trail_head_pos: Vec<usize>,
-    visited_peaks: &mut HashSet<(usize, usize)>,
-) -> u32 {
-    let mut trail_head_score = 0;
-    let dims = [map.len(), map[0].len()];
-    let mut new_pos: Vec<usize>;
-    let mut new_height: u32;
-
-    let curr_height = map[trail_head_pos[0]][trail_head_pos[1]];
-
-    if curr_height == 9 {
-        let trail_head_tup = (trail_head_pos[0], trail_head_pos[1]);
-        match visited_peaks.get(&trail_head_tup) {
-            Some(_) => return 0,
-            None => {
-                visited_peaks.insert(trail_head_tup);
-                return 1;
-            }
-        }
-    }
-
-    for dir in DIRECTIONS.iter().map(Vec::from) {
-        new_pos = trail_head_pos
-            .iter()
-            .zip(dir)
-            .map(|(coord, diff)| (*coord as i32 + diff) as usize)
-            .collect();
-
-        if new_pos[0] >= dims[0] || new_pos[1] >= dims[1] {
-            continue;
-        }
-
-        new_height = map[new_pos[0]][new_pos[1]];
-
-        if new_height.saturating_sub(curr_height) != 1 {
-            continue;
-        }
-
-        trail_head_score += count_peaks_from_trailhead(map, new_pos, visited_peaks);
-    }
-
-    trail_head_score
+/// Walks every trail from a trail head, where each step goes up by exactly one.
+/// All trails to a position have the same length, so the shortest paths are all of them.
+fn explore_trails(map: &Grid<u32>, trail_head_pos: Pos) -> Search<Pos, usize> {
+    bfs(
+        trail_head_pos,
+        |pos| {
+            map.neighbors4(*pos)
+                .filter(|next| map[*next] == map[*pos] + 1)
+                .collect::<Vec<_>>()
+        },
+        |_| false,
+    )
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    // Start from trail head (0).
-    // Look around for trail head + 1.
-    // Start from there.
-    // If trail head is 9 and we haven't visited -> return 1
+fn sum_over_trail_heads(
+    input: &str,
+    score: impl Fn(&Grid<u32>, &Search<Pos, usize>) -> u64,
+) -> u32 {
     let topo_map = parse_input(input);
-    let mut trail_head_score = 0;
 
-    for i in 0..topo_map.len() {
-        for j in 0..topo_map[i].len() {
-            if topo_map[i][j] == 0 {
-                let score = count_peaks_from_trailhead(&topo_map, vec![i, j], &mut HashSet::new());
-                trail_head_score += score;
-            }
-        }
-    }
-
-    Some(trail_head_score)
+    topo_map
+        .find_all(&0)
+        .map(|trail_head_pos| score(&topo_map, &explore_trails(&topo_map, trail_head_pos)))
+        .sum::<u64>() as u32
 }
 
-fn count_trails_from_trailhead(map: &Vec<Vec<u32>>, trail_head_pos: Vec<usize>) -> u32 {
-    let mut trail_head_score = 0;
-    let dims = [map.len(), map[0].len()];
-    let mut new_pos: Vec<usize>;
-    let mut new_height: u32;
-
-    let curr_height = map[trail_head_pos[0]][trail_head_pos[1]];
-
-    if curr_height == 9 {
-        return 1;
-    }
-
-    for dir in DIRECTIONS.iter().map(Vec::from) {
-        new_pos = trail_head_pos
-            .iter()
-            .zip(dir)
-            .map(|(coord, diff)| (*coord as i32 + diff) as usize)
-            .collect();
-
-        if new_pos[0] >= dims[0] || new_pos[1] >= dims[1] {
-            continue;
-        }
-
-        new_height = map[new_pos[0]][new_pos[1]];
-
-        if new_height.saturating_sub(curr_height) != 1 {
-            continue;
-        }
-
-        trail_head_score += count_trails_from_trailhead(map, new_pos);
-    }
-
-    trail_head_score
+pub fn part_one(input: &str) -> Option<u32> {
+    // Count the peaks (9) each trail head (0) reaches.
+    Some(sum_over_trail_heads(input, |map, trails| {
+        trails.reached().filter(|(pos, _)| map[**pos] == 9).count() as u64
+    }))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    // Start from trail head (0).
-    // Look around for trail head + 1.
-    // Start from there.
-    // If trail head is 9 -> return 1
-    let topo_map = parse_input(input);
-    let mut trail_head_score = 0;
-
-    for i in 0..topo_map.len() {
-        for j in 0..topo_map[i].len() {
-            if topo_map[i][j] == 0 {
-                let score = count_trails_from_trailhead(&topo_map, vec![i, j]);
-                trail_head_score += score;
-            }
-        }
-    }
-
-    Some(trail_head_score)
+    // Count the distinct trails from each trail head (0) to any peak (9).
+    Some(sum_over_trail_heads(input, |map, trails| {
+        map.find_all(&9).map(|peak| trails.count_paths(&peak)).sum()
+    }))
 }
 
 #[cfg(test)]
@@ -145,13 +54,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(36));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(81));
     }
 }
@@ -89,9 +89,9 @@ fn transmute_stone(stone: &u64) -> Vec<u64> {
         let left_digits = stone / downshift;
         let right_digits = stone - (left_digits * downshift);
 
-        return vec![left_digits, right_digits];
+        vec![left_digits, right_digits]
     } else {
-        return vec![stone * 2024];
+        vec![stone * 2024]
     }
 }
 
@@ -135,13 +135,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(55312));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(65601038650482));
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, parse = parse_input);
 
 type Position = [usize; 2];
 type Direction = [i32; 2];
@@ -44,7 +44,9 @@ fn prev_diag(dir: Direction) -> Direction {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Vec<char>> {
+type Garden = Vec<Vec<char>>;
+
+fn parse_input(input: &str) -> Garden {
     let mut output = Vec::new();
 
     for line in input.lines() {
@@ -131,7 +133,7 @@ fn map_region(
     (region, perimeter_len)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(garden: &Garden) -> Option<u32> {
     // - Parse input into garden: Vec<Vec<char>>.
     // - Find regions:
     //   - Init regions: Vec<(Vec<(&usize, &usize)>, usize)> (Vector of
@@ -150,7 +152,6 @@ pub fn part_one(input: &str) -> Option<u32> {
     //          - Else: Increment perimeter count.
     //  - For each region in regions: Sum the product of the length of the
     //    perimeter with the number of plots.
-    let garden = parse_input(input);
     let mut regions: Vec<(Vec<Position>, usize)> = Vec::new();
     let mut visited_plots: HashSet<Position> = HashSet::new();
 
@@ -159,7 +160,7 @@ pub fn part_one(input: &str) -> Option<u32> {
             if visited_plots.contains(&[i, j]) {
                 continue;
             } else {
-                regions.push(map_region([i, j], &garden, &mut visited_plots));
+                regions.push(map_region([i, j], garden, &mut visited_plots));
             }
         }
     }
@@ -313,7 +314,7 @@ fn map_region_lazy(
     (region, count_sides(&mut edges))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(garden: &Garden) -> Option<u32> {
     // - Parse as in part one.
     // - Find regions:
     //   - Init regions: Vec<(HashSet<(&usize, &usize)>, usize)> (Set of
@@ -326,7 +327,6 @@ pub fn part_two(input: &str) -> Option<u32> {
     //         perimeter.
     //     - Count sides by walking along each boundary, removing each edge
     //       and counting new corners as sides.
-    let garden = parse_input(input);
     let mut regions: Vec<(Vec<Position>, usize)> = Vec::new();
     let mut visited_plots: HashSet<Position> = HashSet::new();
 
@@ -335,7 +335,7 @@ pub fn part_two(input: &str) -> Option<u32> {
             if visited_plots.contains(&[i, j]) {
                 continue;
             } else {
-                regions.push(map_region_lazy([i, j], &garden, &mut visited_plots));
+                regions.push(map_region_lazy([i, j], garden, &mut visited_plots));
             }
         }
     }
@@ -351,13 +351,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = part_one(&parse_input(&input));
         assert_eq!(result, Some(1930));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = part_two(&parse_input(&input));
         assert_eq!(result, Some(1206));
     }
 }
@@ -214,7 +214,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result =
-            part_one(&advent_of_code::template::read_file("examples", DAY));
+            part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(12));
     }
 }
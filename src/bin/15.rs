@@ -100,9 +100,9 @@ fn get_box_coords_sum(map: &[Vec<char>]) -> u32 {
     let mut coord_sum = 0;
     // Since there is a wall around the map, we don't need to look at the outer
     // fields.
-    for i in 1..(map.len() - 1) {
-        for j in 1..(map.len() - 1) {
-            if map[i][j] == 'O' {
+    for (i, row) in map.iter().enumerate().take(map.len() - 1).skip(1) {
+        for (j, field) in row.iter().enumerate().take(map.len() - 1).skip(1) {
+            if *field == 'O' {
                 coord_sum += (100 * i + j) as u32;
             }
         }
@@ -239,7 +239,7 @@ fn find_boxes_in_dir(
     let new_compl_char = map[new_compl_pos[0]][new_compl_pos[1]];
 
     if new_handle_char == '#' || new_compl_char == '#' {
-        return None;
+        None
     } else {
         let mut output = Vec::new();
 
@@ -267,7 +267,7 @@ fn find_boxes_in_dir(
             output.push([compl_pos, handle_pos]);
         }
 
-        return Some(output);
+        Some(output)
     }
 }
 
@@ -325,7 +325,7 @@ fn movement_with_boxes(
     } else {
         let boxes = match find_boxes_in_dir(&new_pos, map, movement) {
             Some(boxes) => boxes,
-            None => return (),
+            None => return,
         };
 
         let mut unique_boxes: HashSet<[usize; 4]> =
@@ -363,9 +363,9 @@ fn get_wide_box_coords_sum(map: &[Vec<char>]) -> u32 {
 
     // Since there is a wall around the map, we don't need to look at the outer
     // fields.
-    for i in 1..=(map.len() - 1) {
-        for j in 1..=(map[0].len() - 1) {
-            if map[i][j] == '[' {
+    for (i, row) in map.iter().enumerate().skip(1) {
+        for (j, field) in row.iter().enumerate().skip(1) {
+            if *field == '[' {
                 coord_sum += (100 * i + j) as u32;
             }
         }
@@ -392,14 +392,14 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result =
-            part_one(&advent_of_code::template::read_file("examples", DAY));
+            part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(10092));
     }
 
     #[test]
     fn test_part_two() {
         let result =
-            part_two(&advent_of_code::template::read_file("examples", DAY));
+            part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(9021));
     }
 }
@@ -0,0 +1,274 @@
+/// Points, vectors and directions on a 2D grid, with `y` growing downwards like the rows of an input.
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point on a 2D grid. `Point<usize>` indexes a [`Grid`](crate::grid::Grid).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A displacement between two points, e.g. the step of a [`Direction`].
+pub type Vec2 = Point<i64>;
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Point<usize> {
+    /// Moves the point by a vector, returning `None` if it leaves a grid of `width` × `height`.
+    pub fn checked_add(self, v: Vec2, (width, height): (usize, usize)) -> Option<Self> {
+        let x = self.x.checked_add_signed(isize::try_from(v.x).ok()?)?;
+        let y = self.y.checked_add_signed(isize::try_from(v.y).ok()?)?;
+        (x < width && y < height).then_some(Self { x, y })
+    }
+
+    /// Moves the point one step in a direction, returning `None` if it leaves a grid of `width` × `height`.
+    pub fn step(self, direction: Direction, bounds: (usize, usize)) -> Option<Self> {
+        self.checked_add(direction.offset(), bounds)
+    }
+}
+
+macro_rules! impl_manhattan {
+    ($($t:ty => $distance:ty),*) => {$(
+        impl Point<$t> {
+            /// The distance to another point when only moving horizontally and vertically.
+            pub fn manhattan(self, other: Self) -> $distance {
+                self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+            }
+        }
+    )*};
+}
+
+impl_manhattan!(i32 => u32, i64 => u64, isize => usize, u32 => u32, u64 => u64, usize => usize);
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the 8 directions on a grid, in clockwise order from `Up`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The 4 orthogonal directions, clockwise from up.
+    pub const CARDINAL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// All 8 directions, clockwise from up.
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// The direction `eighths` × 45° clockwise of this one.
+    fn rotate(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % 8]
+    }
+
+    /// Turns 90° clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Turns 90° counterclockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    /// Turns 45° clockwise, e.g. from `Up` to `UpRight`.
+    pub fn turn_right_45(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// Turns 45° counterclockwise, e.g. from `Up` to `UpLeft`.
+    pub fn turn_left_45(self) -> Self {
+        self.rotate(7)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// The step of one move in this direction. `Up` decreases `y`.
+    pub fn offset(self) -> Vec2 {
+        let (x, y) = match self {
+            Self::Up => (0, -1),
+            Self::UpRight => (1, -1),
+            Self::Right => (1, 0),
+            Self::DownRight => (1, 1),
+            Self::Down => (0, 1),
+            Self::DownLeft => (-1, 1),
+            Self::Left => (-1, 0),
+            Self::UpLeft => (-1, -1),
+        };
+        Vec2::new(x, y)
+    }
+}
+
+/// Parses the arrows `^`, `>`, `v` and `<` that inputs draw moves with.
+impl TryFrom<char> for Direction {
+    type Error = DirectionFromCharError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' => Ok(Self::Up),
+            '>' => Ok(Self::Right),
+            'v' => Ok(Self::Down),
+            '<' => Ok(Self::Left),
+            _ => Err(DirectionFromCharError(value)),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Direction`] from a character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DirectionFromCharError(pub char);
+
+impl Error for DirectionFromCharError {}
+
+impl Display for DirectionFromCharError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting one of `^>v<`, found `{}`", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Point, Vec2};
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right_45(), Direction::UpLeft);
+        assert_eq!(Direction::UpRight.turn_right(), Direction::DownRight);
+        assert_eq!(Direction::DownLeft.reverse(), Direction::UpRight);
+        assert!(Direction::CARDINAL.iter().all(|d| !d.is_diagonal()));
+    }
+
+    #[test]
+    fn parses_arrows() {
+        let moves: Result<Vec<Direction>, _> = "^>v<".chars().map(Direction::try_from).collect();
+        assert_eq!(
+            moves.unwrap(),
+            [
+                Direction::Up,
+                Direction::Right,
+                Direction::Down,
+                Direction::Left
+            ]
+        );
+        assert!(Direction::try_from('x').is_err());
+    }
+
+    #[test]
+    fn steps_within_bounds() {
+        let origin = Point::new(0_usize, 0);
+        assert_eq!(origin.step(Direction::Up, (3, 3)), None);
+        assert_eq!(
+            origin.step(Direction::DownRight, (3, 3)),
+            Some(Point::new(1, 1))
+        );
+        assert_eq!(origin.checked_add(Vec2::new(3, 0), (3, 3)), None);
+        assert_eq!(
+            origin.checked_add(Vec2::new(2, 2), (3, 3)),
+            Some(Point::new(2, 2))
+        );
+    }
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Point::new(1_i64, -2);
+        assert_eq!(a + Direction::Right.offset(), Point::new(2, -2));
+        assert_eq!(a * 3, Point::new(3, -6));
+        assert_eq!(-a, Point::new(-1, 2));
+        assert_eq!(a.manhattan(Point::new(-2, 2)), 7);
+    }
+}
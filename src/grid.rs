@@ -0,0 +1,523 @@
+/// A rectangular grid of cells, the shape of most Advent of Code inputs.
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::geometry::{Direction, Point};
+
+/// A position in a [`Grid`] as `(x, y)`, where `x` is the column and `y` the row counted from the top.
+pub type Pos = (usize, usize);
+
+/// A rectangular grid of cells, stored row by row.
+///
+/// ```
+/// # use advent_of_code::grid::Grid;
+/// let grid: Grid<char> = "ab\ncd".parse().unwrap();
+/// assert_eq!(grid[(1, 0)], 'b');
+/// assert_eq!(grid.find(&'c'), Some((0, 1)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of the given size with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from its rows. Returns `None` if the rows differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses every character of a text block into a cell, e.g. `Grid::parse_with(input, |c| c.to_digit(10))`.
+    /// Fails on lines of different lengths and on characters that `f` rejects.
+    pub fn parse_with(
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseGridError> {
+        let mut rows = vec![];
+
+        for (y, line) in input.lines().enumerate() {
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(x, c)| {
+                    f(c).ok_or(ParseGridError::InvalidCell {
+                        line: y + 1,
+                        column: x + 1,
+                        cell: c,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if let Some(first) = rows.first().map(Vec::len).filter(|w| *w != row.len()) {
+                return Err(ParseGridError::Ragged {
+                    line: y + 1,
+                    expected: first,
+                    found: row.len(),
+                });
+            }
+
+            rows.push(row);
+        }
+
+        // NOTE: the rows were checked to be of equal length above.
+        Ok(Self::from_rows(rows).unwrap())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The size of the grid as `(width, height)`, e.g. for [`Point::step`].
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Whether a position, which may lie outside of the grid, is inside it.
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        usize::try_from(x).is_ok_and(|x| x < self.width)
+            && usize::try_from(y).is_ok_and(|y| y < self.height)
+    }
+
+    /// The cell at a position, or `None` if it lies outside of the grid.
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, (x, y): Pos) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// The cell at a position that wraps around the edges, e.g. `(-1, 0)` is the last cell of the first row.
+    /// Panics on an empty grid.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        &self[self.wrap(x, y)]
+    }
+
+    /// Wraps a position around the edges of the grid.
+    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    pub fn wrap(&self, x: isize, y: isize) -> Pos {
+        (
+            x.rem_euclid(self.width as isize) as usize,
+            y.rem_euclid(self.height as isize) as usize,
+        )
+    }
+
+    /// Moves a position by an offset, returning `None` if it leaves the grid.
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        (pos.0 < self.width && pos.1 < self.height).then_some(pos)
+    }
+
+    /// All positions of the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        (0..self.height).flat_map(move |y| (0..self.width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The cells of a row, left to right. A row outside of the grid has no cells.
+    pub fn row(&self, y: usize) -> &[T] {
+        let width = if y < self.height { self.width } else { 0 };
+        &self.cells[y * width..(y + 1) * width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// The cells of a column, top to bottom. A column outside of the grid has no cells.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(height)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The diagonals running from the top left to the bottom right,
+    /// starting with the one in the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = Vec<&T>> {
+        (1..=self.diagonal_count()).map(|i| {
+            // the diagonal starts in the first column for `i <= height`, in the first row otherwise.
+            let (x, y) = (i.saturating_sub(self.height), self.height.saturating_sub(i));
+            (0..)
+                .map_while(|step| self.get((x + step, y + step)))
+                .collect()
+        })
+    }
+
+    /// The diagonals running from the top right to the bottom left,
+    /// starting with the one in the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Vec<&T>> {
+        (1..=self.diagonal_count()).map(|i| {
+            // the diagonal starts in the first row for `i <= width`, in the last column otherwise.
+            let (x, y) = (i.min(self.width) - 1, i.saturating_sub(self.width));
+            (0..)
+                .map_while(|step| {
+                    let x = x.checked_sub(step)?;
+                    self.get((x, y + step))
+                })
+                .collect()
+        })
+    }
+
+    /// The number of diagonals in either direction. A grid without cells has none.
+    fn diagonal_count(&self) -> usize {
+        if self.cells.is_empty() {
+            0
+        } else {
+            self.width + self.height - 1
+        }
+    }
+
+    /// The up to 4 orthogonal neighbors of a position inside of the grid, clockwise from up.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbors(pos, Direction::CARDINAL)
+    }
+
+    /// The up to 8 neighbors, including the diagonal ones, of a position inside of the grid, clockwise from up.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbors(pos, Direction::ALL)
+    }
+
+    fn neighbors<const N: usize>(
+        &self,
+        pos: Pos,
+        directions: [Direction; N],
+    ) -> impl Iterator<Item = Pos> + '_ {
+        directions
+            .into_iter()
+            .filter_map(move |direction| Point::from(pos).step(direction, self.bounds()))
+            .map(Pos::from)
+    }
+
+    /// The position of the first cell, row by row, that holds a value.
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.position(|cell| cell == value)
+    }
+
+    /// The positions of all cells that hold a value.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    /// The position of the first cell, row by row, that matches a predicate.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// Creates a grid of the same size by mapping every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |(x, y)| (y, x))
+    }
+
+    /// Rotates the grid by 90° clockwise.
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |(x, y)| (y, self.height - 1 - x))
+    }
+
+    /// Rotates the grid by 90° counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |(x, y)| (self.width - 1 - y, x))
+    }
+
+    /// Creates a grid of a new size, taking each cell from the position `source` maps it to.
+    fn rebuild(&self, width: usize, height: usize, source: impl Fn(Pos) -> Pos) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|pos| self[source(pos)].clone())
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "position {pos:?} is outside of the {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is outside of the {width}x{height} grid"))
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<usize>) -> &Self::Output {
+        &self[Pos::from(point)]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<usize>) -> &mut Self::Output {
+        &mut self[Pos::from(point)]
+    }
+}
+
+/// Prints the cells of each row next to each other, one row per line.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, Some)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing a [`Grid`]. Lines and columns count from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// A line is longer or shorter than the first one.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character that does not map to a cell.
+    InvalidCell {
+        line: usize,
+        column: usize,
+        cell: char,
+    },
+}
+
+impl Error for ParseGridError {}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expecting line {line} to have {expected} cells like the first one, found {found}"
+            ),
+            Self::InvalidCell { line, column, cell } => {
+                write!(
+                    f,
+                    "unexpected cell `{cell}` at line {line}, column {column}"
+                )
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, ParseGridError};
+
+    fn grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        let digits = Grid::parse_with("12\n34", |c| c.to_digit(10)).unwrap();
+        assert_eq!(digits[(0, 1)], 3);
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert_eq!(
+            "ab\nc".parse::<Grid<char>>(),
+            Err(ParseGridError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::parse_with("12\n3x", |c| c.to_digit(10)),
+            Err(ParseGridError::InvalidCell {
+                line: 2,
+                column: 2,
+                cell: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn indexes_checked_and_wrapping() {
+        let grid = grid();
+        assert_eq!(grid.get((3, 0)), None);
+        assert!(!grid.contains(-1, 0));
+        assert_eq!(*grid.get_wrapping(-1, 0), 'c');
+        assert_eq!(*grid.get_wrapping(3, 3), 'd');
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (1, 1)), Some((1, 1)));
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = grid();
+        let column: String = grid.column(1).collect();
+        assert_eq!(column, "be");
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.rows().count(), 2);
+
+        let diagonals: Vec<String> = grid.diagonals().map(|d| d.into_iter().collect()).collect();
+        assert_eq!(diagonals, ["d", "ae", "bf", "c"]);
+
+        let anti_diagonals: Vec<String> = grid
+            .anti_diagonals()
+            .map(|d| d.into_iter().collect())
+            .collect();
+        assert_eq!(anti_diagonals, ["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    fn iterates_lines_of_empty_grids() {
+        assert!(grid().row(2).is_empty());
+
+        let grid = Grid::new(0, 3, '.');
+        assert!(grid.row(1).is_empty());
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.columns().count(), 0);
+        assert_eq!(grid.diagonals().count(), 0);
+        assert_eq!(grid.anti_diagonals().count(), 0);
+    }
+
+    #[test]
+    fn indexes_with_points() {
+        use crate::geometry::{Direction, Point};
+
+        let grid = grid();
+        let point = Point::new(1, 0)
+            .step(Direction::Down, grid.bounds())
+            .unwrap();
+        assert_eq!(grid[point], 'e');
+    }
+
+    #[test]
+    fn finds_neighbors() {
+        let grid = grid();
+        let neighbors: Vec<_> = grid.neighbors4((0, 0)).collect();
+        assert_eq!(neighbors, [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors8((1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn finds_values() {
+        let grid: Grid<char> = "a.a\n.a.".parse().unwrap();
+        assert_eq!(grid.find(&'.'), Some((1, 0)));
+        assert_eq!(grid.find_all(&'a').count(), 3);
+        assert_eq!(grid.find(&'x'), None);
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+}
@@ -1,13 +1,23 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, read, scaffold, solve, status, time, verify, watch,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
+use advent_of_code::template::Puzzle;
 #[cfg(feature = "today")]
 use std::process;
 
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::input::InputSource;
+    use advent_of_code::template::output::OutputFormat;
+    use advent_of_code::template::{Day, Year};
+    use std::path::PathBuf;
     use std::process;
 
     pub enum AppArguments {
@@ -21,42 +31,88 @@ mod args {
             day: Day,
             download: bool,
             overwrite: bool,
+            force: bool,
+            template: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            format: OutputFormat,
+            watch: bool,
+            test: bool,
+            input: InputSource,
         },
         All {
             release: bool,
+            jobs: Option<usize>,
+            format: OutputFormat,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            jobs: Option<usize>,
+            compare_threshold: Option<f64>,
+            history: Option<Day>,
+            format: OutputFormat,
+        },
+        Verify {
+            day: Option<Day>,
+            release: bool,
+        },
+        ExtractExamples {
+            day: Day,
+            overwrite: bool,
         },
+        Status,
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    /// Parses the command and the year it applies to.
+    /// Every command takes a `--year`, which defaults to the `AOC_YEAR` set in `.cargo/config.toml`.
+    pub fn parse() -> Result<(Year, AppArguments), Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        let year = args
+            .opt_value_from_str("--year")?
+            .or_else(Year::from_env)
+            .ok_or("no year given, pass `--year` or set `AOC_YEAR` to a year of 2015 or later")?;
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_fn("--jobs", parse_jobs)?,
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let jobs = args.opt_value_from_fn("--jobs", parse_jobs)?;
+                let compare = args.contains("--compare");
+                let threshold = args.opt_value_from_fn("--threshold", parse_threshold)?;
+                let history = args.opt_value_from_str("--history")?;
+                let format = args.opt_value_from_str("--format")?.unwrap_or_default();
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    jobs,
+                    compare_threshold: compare
+                        .then(|| threshold.unwrap_or(DEFAULT_REGRESSION_THRESHOLD)),
+                    history,
+                    format,
                 }
             }
+            Some("verify") => AppArguments::Verify {
+                release: args.contains("--release"),
+                day: args.opt_free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -67,13 +123,64 @@ mod args {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                force: args.contains("--force"),
+                template: args.opt_value_from_str("--template")?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            Some("solve") => {
+                let watch = args.contains("--watch");
+                let submit = args.opt_value_from_str("--submit")?;
+                let release = args.contains("--release");
+                let dhat = args.contains("--dhat");
+                let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+                let test = args.contains("--test");
+                let example = args.contains("--example");
+                let input_path: Option<PathBuf> = args.opt_value_from_str("--input")?;
+                let day = args.free_from_str()?;
+
+                let input = match (example, input_path) {
+                    (true, Some(_)) => {
+                        return Err("`--example` cannot be combined with `--input`".into())
+                    }
+                    // the example number follows the day, e.g. `solve 1 --example 2`.
+                    (true, None) => InputSource::Example(args.opt_free_from_str()?),
+                    (false, Some(path)) if path.as_os_str() == "-" => InputSource::Stdin,
+                    (false, Some(path)) => InputSource::File(path),
+                    (false, None) => InputSource::Puzzle,
+                };
+
+                if watch && submit.is_some() {
+                    return Err("`--watch` cannot be combined with `--submit`".into());
+                }
+                if submit.is_some() && !input.is_puzzle() {
+                    return Err("only answers to the puzzle input can be submitted".into());
+                }
+
+                AppArguments::Solve {
+                    day,
+                    release,
+                    submit,
+                    dhat,
+                    format,
+                    watch,
+                    test,
+                    input,
+                }
+            }
+            Some("examples") => match args.subcommand()?.as_deref() {
+                Some("extract") => AppArguments::ExtractExamples {
+                    day: args.free_from_str()?,
+                    overwrite: args.contains("--overwrite"),
+                },
+                Some(x) => {
+                    eprintln!("Unknown examples command: {x}");
+                    process::exit(1);
+                }
+                None => {
+                    eprintln!("No examples command specified, expecting `extract`.");
+                    process::exit(1);
+                }
             },
+            Some("status") => AppArguments::Status,
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -91,7 +198,50 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        if let Some(day) = app_args.day().filter(|day| *day > year.last_day()) {
+            return Err(format!(
+                "day {day} is not part of {year}, which only has {} days",
+                year.day_count()
+            )
+            .into());
+        }
+
+        Ok((year, app_args))
+    }
+
+    impl AppArguments {
+        /// The day a command is given, if any.
+        fn day(&self) -> Option<Day> {
+            match self {
+                Self::Download { day }
+                | Self::Read { day }
+                | Self::Scaffold { day, .. }
+                | Self::Solve { day, .. }
+                | Self::ExtractExamples { day, .. } => Some(*day),
+                Self::Time { day, history, .. } => day.or(*history),
+                Self::Verify { day, .. } => *day,
+                Self::All { .. } | Self::Status => None,
+                #[cfg(feature = "today")]
+                Self::Today => None,
+            }
+        }
+    }
+
+    /// Percentage by which a part may get slower before `time --compare` fails.
+    const DEFAULT_REGRESSION_THRESHOLD: f64 = 10.0;
+
+    fn parse_threshold(s: &str) -> Result<f64, String> {
+        match s.parse() {
+            Ok(threshold) if threshold >= 0.0 => Ok(threshold),
+            _ => Err("expecting a non-negative threshold in percent".into()),
+        }
+    }
+
+    fn parse_jobs(s: &str) -> Result<usize, String> {
+        match s.parse() {
+            Ok(jobs) if jobs > 0 => Ok(jobs),
+            _ => Err("expecting a number of jobs greater than 0".into()),
+        }
     }
 }
 
@@ -101,19 +251,52 @@ fn main() {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+        Ok((year, args)) => match args {
+            AppArguments::Time {
+                history: Some(day), ..
+            } => time::history(year, day),
+            AppArguments::All {
+                release,
+                jobs,
+                format,
+            } => all::handle(year, release, jobs, solutions::SOLUTIONS, format),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                jobs,
+                compare_threshold,
+                history: None,
+                format,
+            } => time::handle(
+                year,
+                day,
+                all,
+                store,
+                jobs,
+                compare_threshold,
+                solutions::SOLUTIONS,
+                format,
+            ),
+            AppArguments::Verify { day, release } => verify::handle(year, day, release),
+            AppArguments::Download { day } => download::handle(Puzzle::new(year, day)),
+            AppArguments::Read { day } => read::handle(Puzzle::new(year, day)),
+            AppArguments::ExtractExamples { day, overwrite } => {
+                examples::extract(Puzzle::new(year, day), overwrite);
+            }
+            AppArguments::Status => status::handle(year, solutions::SOLUTIONS),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                force,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                let puzzle = Puzzle::new(year, day);
+                let template = template.as_deref().unwrap_or(scaffold::DEFAULT_TEMPLATE);
+                scaffold::handle(puzzle, template, overwrite, force);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
             }
             AppArguments::Solve {
@@ -121,19 +304,39 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                format,
+                watch: false,
+                input,
+                ..
+            } => solve::handle(
+                Puzzle::new(year, day),
+                release,
+                dhat,
+                submit,
+                format,
+                &input,
+            ),
+            AppArguments::Solve {
+                day,
+                release,
+                test,
+                input,
+                ..
+            } => watch::handle(Puzzle::new(year, day), release, test, &input),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
+                match Day::today(year) {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = Puzzle::new(year, day);
+                        scaffold::handle(puzzle, scaffold::DEFAULT_TEMPLATE, false, false);
+                        download::handle(puzzle);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run between december 1 and december {} \
+                            during the {year} event. Please use `scaffold` with a specific day.",
+                            year.last_day().into_inner()
                         );
                         process::exit(1)
                     }
@@ -0,0 +1,279 @@
+/// Helpers for the formats Advent of Code inputs tend to come in.
+/// Fallible helpers report where the input is malformed with a [`ParseError`].
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::grid::{Grid, ParseGridError};
+
+/// All integers in a text, with a `-` directly in front of one as its sign, e.g. `p=0,-4 v=3,-3`.
+/// A `-` between two digits is taken as a range, so `1-3` yields `1` and `3`.
+/// A number that does not fit `T`, e.g. a negative one for an unsigned type, is an error at its column.
+pub fn integers<T: FromStr>(s: &str) -> Result<Vec<T>, ParseError> {
+    let bytes = s.as_bytes();
+    let mut output = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let is_negative =
+            i > 0 && bytes[i - 1] == b'-' && (i < 2 || !bytes[i - 2].is_ascii_digit());
+        let start = if is_negative { i - 1 } else { i };
+
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        output.push(parse_token(&s[start..i], start)?);
+    }
+
+    Ok(output)
+}
+
+/// A block of lines, e.g. one of the [`sections`] of an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub text: &'a str,
+    /// The line number of the first line in the whole input, counted from 1.
+    pub line: usize,
+}
+
+impl<'a> Section<'a> {
+    /// Parses every line of the section, numbering errors by their line in the whole input.
+    pub fn lines_with<T>(
+        &self,
+        mut f: impl FnMut(&'a str) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| f(line).map_err(|e| e.at_line(self.line + i)))
+            .collect()
+    }
+}
+
+/// Splits an input into the sections that blank lines separate, e.g. the rules and updates of 2024 day 5.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut output = vec![];
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for (i, line) in input.split_inclusive('\n').enumerate() {
+        let is_blank = line.trim().is_empty();
+        match start {
+            None if !is_blank => start = Some((offset, i + 1)),
+            Some((start_offset, line_number)) if is_blank => {
+                output.push(Section {
+                    text: input[start_offset..offset].trim_end(),
+                    line: line_number,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+
+    if let Some((start_offset, line_number)) = start {
+        output.push(Section {
+            text: input[start_offset..].trim_end(),
+            line: line_number,
+        });
+    }
+
+    output
+}
+
+/// Parses every line of an input, numbering errors by their line.
+pub fn lines_with<'a, T>(
+    input: &'a str,
+    f: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    Section {
+        text: input,
+        line: 1,
+    }
+    .lines_with(f)
+}
+
+/// Parses a line like `190: 10 19` into its key and the values after it.
+pub fn key_values<K: FromStr, V: FromStr>(line: &str) -> Result<(K, Vec<V>), ParseError> {
+    let Some((key, values)) = line.split_once(':') else {
+        return Err(ParseError::new(1, "expecting `key: values`, found no `:`"));
+    };
+
+    let values_offset = key.len() + 1;
+    let key = parse_token(key.trim(), key.len() - key.trim_start().len())?;
+    let values = tokens(values)
+        .map(|(offset, value)| parse_token(value, values_offset + offset))
+        .collect::<Result<_, _>>()?;
+
+    Ok((key, values))
+}
+
+/// The tokens of a text that ASCII whitespace separates, with their byte offset in the text.
+fn tokens(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.split(|c: char| c.is_ascii_whitespace())
+        .scan(0, |offset, token| {
+            let start = *offset;
+            *offset += token.len() + 1;
+            Some((start, token))
+        })
+        .filter(|(_, token)| !token.is_empty())
+}
+
+/// Parses a token at a byte offset of its line, reporting that column if it is malformed.
+fn parse_token<T: FromStr>(token: &str, offset: usize) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::new(offset + 1, format!("unexpected `{token}`")))
+}
+
+/// Parses a grid of single digits, e.g. the heights of 2024 day 10.
+pub fn digit_grid(input: &str) -> Result<Grid<u32>, ParseError> {
+    Ok(Grid::parse_with(input, |c| c.to_digit(10))?)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error in an input, at a line and column that count from 1.
+/// Helpers that only see a single line leave the line out, [`lines_with`] fills it in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Sets the line of the error, unless it already has one.
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line.get_or_insert(line);
+        self
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}, column {}: {}", self.column, self.message),
+            None => write!(f, "column {}: {}", self.column, self.message),
+        }
+    }
+}
+
+impl From<ParseGridError> for ParseError {
+    fn from(value: ParseGridError) -> Self {
+        let (line, column, message) = match value {
+            ParseGridError::Ragged {
+                line,
+                expected,
+                found,
+            } => (
+                line,
+                expected.min(found) + 1,
+                format!("expecting {expected} cells like the first line, found {found}"),
+            ),
+            ParseGridError::InvalidCell { line, column, cell } => {
+                (line, column, format!("unexpected cell `{cell}`"))
+            }
+        };
+
+        Self {
+            line: Some(line),
+            column,
+            message,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{digit_grid, integers, key_values, lines_with, sections, ParseError};
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(integers::<i32>("p=0,-4 v=3,-3"), Ok(vec![0, -4, 3, -3]));
+        assert_eq!(integers::<u32>("1-3 a: 7"), Ok(vec![1, 3, 7]));
+        assert_eq!(integers::<i64>("Button A: X+94, Y+34"), Ok(vec![94, 34]));
+        assert_eq!(
+            integers::<u32>("x=-2, y=5"),
+            Err(ParseError::new(3, "unexpected `-2`"))
+        );
+        assert_eq!(
+            integers::<u8>("1 300").unwrap_err().to_string(),
+            "column 3: unexpected `300`"
+        );
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "47|53\n97|13\n\n75,47\n\n\n1,2\n";
+        let sections = sections(input);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].text, "47|53\n97|13");
+        assert_eq!(sections[1].line, 4);
+        assert_eq!(sections[2].text, "1,2");
+        assert_eq!(sections[2].line, 7);
+    }
+
+    #[test]
+    fn parses_key_values() {
+        let (key, values): (u64, Vec<u64>) = key_values("190: 10 19").unwrap();
+        assert_eq!(key, 190);
+        assert_eq!(values, [10, 19]);
+
+        let error = lines_with("3267: 81 40 27\n83: 17 x5", key_values::<u64, u64>).unwrap_err();
+        assert_eq!(
+            error,
+            ParseError {
+                line: Some(2),
+                column: 8,
+                message: "unexpected `x5`".into()
+            }
+        );
+        assert_eq!(error.to_string(), "line 2, column 8: unexpected `x5`");
+        assert_eq!(
+            key_values::<u64, u64>(" 7:  1\t2 3x").unwrap_err(),
+            ParseError::new(10, "unexpected `3x`")
+        );
+        assert_eq!(
+            key_values::<u64, u64>(" k: 1").unwrap_err(),
+            ParseError::new(2, "unexpected `k`")
+        );
+    }
+
+    #[test]
+    fn numbers_section_lines() {
+        let input = "a\n\n1: 2\n3: y";
+        let error = sections(input)[1]
+            .lines_with(key_values::<u32, u32>)
+            .unwrap_err();
+        assert_eq!(error.line, Some(4));
+    }
+
+    #[test]
+    fn parses_digit_grids() {
+        assert_eq!(digit_grid("01\n23").unwrap()[(1, 1)], 3);
+        assert_eq!(
+            digit_grid("01\n2.").unwrap_err().to_string(),
+            "line 2, column 2: unexpected cell `.`"
+        );
+    }
+}
@@ -0,0 +1,383 @@
+/// Shortest path searches over any state type, with the successors of a state given by a closure.
+///
+/// Every search remembers all predecessors that reach a state at its lowest cost,
+/// so one path, all shortest paths or their number can be taken from the result.
+use std::cell::OnceCell;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Breadth-first search, where every step costs 1.
+/// The search ends at the first states that `is_goal` accepts, or once everything reachable is explored.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    astar(
+        start,
+        |state| successors(state).into_iter().map(|next| (next, 1)),
+        |_| 0,
+        is_goal,
+    )
+}
+
+/// Dijkstra's algorithm, for successors that come with a non-negative cost.
+/// The search ends at the first states that `is_goal` accepts, or once everything reachable is explored.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// A* search, which explores states in the order of their cost plus the `heuristic` estimate of the rest.
+/// The heuristic must never overestimate and must be consistent for the shortest paths to be complete.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search {
+        states: vec![],
+        index: HashMap::new(),
+        costs: vec![],
+        predecessors: vec![],
+        goals: vec![],
+        counts: OnceCell::new(),
+    };
+
+    let start_index = search.insert(start, C::default());
+    let mut is_done = vec![false];
+    let mut queue = BinaryHeap::from([Reverse((
+        heuristic(&search.states[start_index]),
+        C::default(),
+        start_index,
+    ))]);
+
+    while let Some(Reverse((_, cost, current))) = queue.pop() {
+        if is_done[current] || cost > search.costs[current] {
+            continue;
+        }
+
+        // every state that is popped after the goals is more expensive, so it cannot lead to another goal.
+        if search.goal_cost().is_some_and(|goal_cost| cost > goal_cost) {
+            break;
+        }
+
+        is_done[current] = true;
+
+        if is_goal(&search.states[current]) {
+            search.goals.push(current);
+            continue;
+        }
+
+        for (next, step) in successors(&search.states[current]) {
+            let next_cost = cost + step;
+
+            match search.index.get(&next).copied() {
+                None => {
+                    let estimate = next_cost + heuristic(&next);
+                    let index = search.insert(next, next_cost);
+                    search.predecessors[index].push(current);
+                    is_done.push(false);
+                    queue.push(Reverse((estimate, next_cost, index)));
+                }
+                Some(index) if next_cost < search.costs[index] => {
+                    search.costs[index] = next_cost;
+                    search.predecessors[index] = vec![current];
+                    let estimate = next_cost + heuristic(&search.states[index]);
+                    queue.push(Reverse((estimate, next_cost, index)));
+                }
+                // with zero-cost steps, this can be a state that is already done.
+                Some(index) if next_cost == search.costs[index] => {
+                    search.predecessors[index].push(current);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    search
+}
+
+/// The outcome of a search: the lowest cost of every state it reached and how it got there.
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+    states: Vec<S>,
+    index: HashMap<S, usize>,
+    costs: Vec<C>,
+    /// The states a state is reached from at its lowest cost.
+    predecessors: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+    /// The number of shortest paths to each state, only counted once they are asked for.
+    counts: OnceCell<Vec<u64>>,
+}
+
+impl<S, C> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy,
+{
+    fn insert(&mut self, state: S, cost: C) -> usize {
+        let index = self.states.len();
+        self.index.insert(state.clone(), index);
+        self.states.push(state);
+        self.costs.push(cost);
+        self.predecessors.push(vec![]);
+        index
+    }
+
+    /// Counts the shortest paths to every state as the sum of the counts of its predecessors.
+    /// Counts saturate at `u64::MAX`, which is also the count of states on a cycle of zero-cost steps.
+    fn counts(&self) -> &[u64] {
+        self.counts.get_or_init(|| {
+            let mut counts: Vec<Option<u64>> = vec![None; self.states.len()];
+            let mut is_visiting = vec![false; self.states.len()];
+
+            for root in 0..self.states.len() {
+                let mut stack = vec![root];
+
+                while let Some(&current) = stack.last() {
+                    if counts[current].is_some() {
+                        stack.pop();
+                        continue;
+                    }
+
+                    is_visiting[current] = true;
+
+                    let pending: Vec<usize> = self.predecessors[current]
+                        .iter()
+                        .copied()
+                        .filter(|&previous| counts[previous].is_none() && !is_visiting[previous])
+                        .collect();
+
+                    if !pending.is_empty() {
+                        stack.extend(pending);
+                        continue;
+                    }
+
+                    // only the start has no predecessors. a predecessor that is still visited closes a cycle.
+                    let count = match self.predecessors[current].as_slice() {
+                        [] => 1,
+                        predecessors => predecessors.iter().fold(0_u64, |sum, &previous| {
+                            sum.saturating_add(counts[previous].unwrap_or(u64::MAX))
+                        }),
+                    };
+
+                    counts[current] = Some(count);
+                    is_visiting[current] = false;
+                    stack.pop();
+                }
+            }
+
+            counts.into_iter().map(|count| count.unwrap_or(0)).collect()
+        })
+    }
+
+    /// The goals that were reached, all at the lowest cost of any goal.
+    pub fn goals(&self) -> impl Iterator<Item = &S> {
+        self.goals.iter().map(|&i| &self.states[i])
+    }
+
+    /// The cost of the cheapest goal, or `None` if no goal was reached.
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goals.first().map(|&i| self.costs[i])
+    }
+
+    /// The lowest cost of a state, or `None` if it was not reached.
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.index.get(state).map(|&i| self.costs[i])
+    }
+
+    /// Every state that was reached with its lowest cost.
+    pub fn reached(&self) -> impl Iterator<Item = (&S, C)> {
+        self.states.iter().zip(self.costs.iter().copied())
+    }
+
+    /// The number of distinct shortest paths from the start to a state, `0` if it was not reached.
+    /// The number saturates at `u64::MAX`, e.g. for the many paths through an open grid.
+    pub fn count_paths(&self, state: &S) -> u64 {
+        self.index.get(state).map_or(0, |&i| self.counts()[i])
+    }
+
+    /// One shortest path from the start to a state, both included.
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        let mut current = *self.index.get(state)?;
+        let mut path = vec![self.states[current].clone()];
+
+        while let Some(&previous) = self.predecessors[current].first() {
+            path.push(self.states[previous].clone());
+            current = previous;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// All shortest paths from the start to a state. Their number can grow exponentially, see [`Self::count_paths`].
+    /// Steps must cost more than nothing, as a cycle of zero-cost steps makes for endless paths.
+    pub fn paths(&self, state: &S) -> Vec<Vec<S>> {
+        let Some(&index) = self.index.get(state) else {
+            return vec![];
+        };
+
+        let mut paths = vec![];
+        let mut stack = vec![vec![index]];
+
+        while let Some(path) = stack.pop() {
+            // NOTE: paths are built backwards from the state and are never empty.
+            let last = *path.last().unwrap();
+            if self.predecessors[last].is_empty() {
+                paths.push(path.iter().rev().map(|&i| self.states[i].clone()).collect());
+                continue;
+            }
+
+            for &previous in &self.predecessors[last] {
+                let mut path = path.clone();
+                path.push(previous);
+                stack.push(path);
+            }
+        }
+
+        paths
+    }
+
+    /// Every state on any shortest path from the start to one of the given states.
+    pub fn on_shortest_paths<'a>(
+        &'a self,
+        states: impl IntoIterator<Item = &'a S>,
+    ) -> HashSet<&'a S> {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<usize> = states
+            .into_iter()
+            .filter_map(|state| self.index.get(state).copied())
+            .collect();
+
+        while let Some(current) = stack.pop() {
+            if seen.insert(current) {
+                stack.extend(&self.predecessors[current]);
+            }
+        }
+
+        seen.into_iter().map(|i| &self.states[i]).collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, dijkstra};
+
+    /// A diamond: 0 -> 1 -> 3 and 0 -> 2 -> 3, followed by 3 -> 4.
+    fn diamond(state: &u32) -> Vec<u32> {
+        match state {
+            0 => vec![1, 2],
+            1 | 2 => vec![3],
+            3 => vec![4],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn searches_breadth_first() {
+        let search = bfs(0, diamond, |s| *s == 4);
+        assert_eq!(search.goal_cost(), Some(3));
+        assert_eq!(search.cost(&3), Some(2));
+        assert_eq!(search.path(&4), Some(vec![0, 1, 3, 4]));
+        assert_eq!(search.count_paths(&4), 2);
+        assert_eq!(search.paths(&4).len(), 2);
+        assert_eq!(search.on_shortest_paths(search.goals()).len(), 5);
+    }
+
+    #[test]
+    fn explores_everything_without_goal() {
+        let search = bfs(0, diamond, |_| false);
+        assert_eq!(search.reached().count(), 5);
+        assert_eq!(search.goal_cost(), None);
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        // going through 1 is cheaper than the direct step to 3.
+        let successors = |s: &u32| match s {
+            0 => vec![(1, 1), (3, 5)],
+            1 => vec![(2, 1), (3, 3)],
+            2 => vec![(3, 2)],
+            _ => vec![],
+        };
+
+        let search = dijkstra(0, successors, |s| *s == 3);
+        assert_eq!(search.goal_cost(), Some(4));
+        assert_eq!(search.count_paths(&3), 2);
+
+        let mut paths = search.paths(&3);
+        paths.sort();
+        assert_eq!(paths, [vec![0, 1, 2, 3], vec![0, 1, 3]]);
+    }
+
+    #[test]
+    fn saturates_path_counts() {
+        // every monotone path through an open 40x40 grid is a shortest one, far more than fit a `u64`.
+        let successors = |&(x, y): &(u32, u32)| {
+            [(x + 1, y), (x, y + 1)]
+                .into_iter()
+                .filter(|(x, y)| *x < 40 && *y < 40)
+        };
+
+        let search = bfs((0, 0), successors, |_| false);
+        assert_eq!(search.cost(&(39, 39)), Some(78));
+        assert_eq!(search.count_paths(&(39, 39)), u64::MAX);
+    }
+
+    #[test]
+    fn counts_paths_over_zero_cost_steps() {
+        // 2 is done before the free step from 1 reaches it at the same cost.
+        let successors = |s: &u32| match s {
+            0 => vec![(1, 1), (2, 1)],
+            1 => vec![(2, 0)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        };
+
+        let search = dijkstra(0, successors, |s| *s == 3);
+        assert_eq!(search.goal_cost(), Some(2));
+        assert_eq!(search.count_paths(&2), 2);
+        assert_eq!(search.count_paths(&3), 2);
+    }
+
+    #[test]
+    fn searches_with_heuristic() {
+        let target = (3_i32, 3_i32);
+        let successors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x, y + 1)]
+                .into_iter()
+                .filter(|(x, y)| *x <= 3 && *y <= 3)
+                .map(|next| (next, 1))
+        };
+        let manhattan = |&(x, y): &(i32, i32)| (target.0 - x) + (target.1 - y);
+
+        let search = astar((0, 0), successors, manhattan, |s| *s == target);
+        assert_eq!(search.goal_cost(), Some(6));
+        // every monotone path on a 4x4 grid is a shortest one: 6 choose 3.
+        assert_eq!(search.count_paths(&target), 20);
+    }
+}
@@ -0,0 +1,323 @@
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::Verdict;
+use crate::template::{workspace_path, Day, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Represents a single submitted answer and the verdict it received.
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Ledger of every answer submitted so far for one year, stored at `data/{year}/answers.json`.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let data_dir = workspace_path(year.data_dir());
+        fs::create_dir_all(&data_dir)?;
+        let mut file = fs::File::create(data_dir.join(ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns an empty ledger.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(workspace_path(year.data_dir()).join(ANSWERS_FILE_NAME))
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Record the verdict for an answer, replacing an earlier verdict for the same answer.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, verdict: Verdict) {
+        self.data
+            .retain(|a| !(a.day == day && a.part == part && a.answer == answer));
+        self.data.push(Answer {
+            day,
+            part,
+            answer: answer.into(),
+            verdict,
+        });
+    }
+
+    fn for_part(&self, day: Day, part: u8) -> impl Iterator<Item = &Answer> {
+        self.data
+            .iter()
+            .filter(move |a| a.day == day && a.part == part)
+    }
+
+    /// The answer that was accepted for a part, if any.
+    pub fn correct(&self, day: Day, part: u8) -> Option<&str> {
+        self.for_part(day, part)
+            .find(|a| a.verdict == Verdict::Correct)
+            .map(|a| a.answer.as_str())
+    }
+
+    /// The verdict a given answer received, if it was submitted before.
+    pub fn verdict(&self, day: Day, part: u8, answer: &str) -> Option<Verdict> {
+        self.for_part(day, part)
+            .find(|a| a.answer == answer)
+            .map(|a| a.verdict)
+    }
+
+    /// Checks a numeric answer against the recorded too-high / too-low answers.
+    /// Returns a description of the violated bound if the answer cannot be correct.
+    pub fn bound_violation(&self, day: Day, part: u8, answer: &str) -> Option<String> {
+        let value = answer.parse::<i128>().ok()?;
+        let numeric = |verdict: Verdict| {
+            self.for_part(day, part)
+                .filter(move |a| a.verdict == verdict)
+                .filter_map(|a| a.answer.parse::<i128>().ok())
+        };
+
+        if let Some(too_high) = numeric(Verdict::TooHigh).filter(|x| value >= *x).min() {
+            return Some(format!(
+                "{answer} is not below {too_high}, which is too high"
+            ));
+        }
+
+        if let Some(too_low) = numeric(Verdict::TooLow).filter(|x| value <= *x).max() {
+            return Some(format!("{answer} is not above {too_low}, which is too low"));
+        }
+
+        None
+    }
+}
+
+/// Whether a verdict rules the answer out for good.
+pub fn is_wrong(verdict: Verdict) -> bool {
+    matches!(
+        verdict,
+        Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn verdict_to_str(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Correct => "correct",
+        Verdict::Incorrect => "incorrect",
+        Verdict::TooHigh => "too_high",
+        Verdict::TooLow => "too_low",
+        Verdict::Throttled => "throttled",
+        Verdict::AlreadySolved => "already_solved",
+        Verdict::Unknown => "unknown",
+    }
+}
+
+fn verdict_from_str(s: &str) -> Option<Verdict> {
+    match s {
+        "correct" => Some(Verdict::Correct),
+        "incorrect" => Some(Verdict::Incorrect),
+        "too_high" => Some(Verdict::TooHigh),
+        "too_low" => Some(Verdict::TooLow),
+        "throttled" => Some(Verdict::Throttled),
+        "already_solved" => Some(Verdict::AlreadySolved),
+        "unknown" => Some(Verdict::Unknown),
+        _ => None,
+    }
+}
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(verdict_to_str(value.verdict).into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| **part == 1.0 || **part == 2.0)
+            .map(|part| *part as u8)
+            .ok_or("Expected answer.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected answer.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| verdict_from_str(v))
+            .ok_or("Expected answer.verdict to be a known verdict.")?;
+
+        Ok(Answer {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{day, template::aoc_client::Verdict};
+
+    use super::{Answer, Answers};
+
+    fn get_mock_answers() -> Answers {
+        let answer = |part, answer: &str, verdict| Answer {
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            verdict,
+        };
+
+        Answers {
+            data: vec![
+                answer(1, "100", Verdict::TooHigh),
+                answer(1, "10", Verdict::TooLow),
+                answer(1, "50", Verdict::TooHigh),
+                answer(1, "42", Verdict::Incorrect),
+                answer(2, "7", Verdict::Correct),
+            ],
+        }
+    }
+
+    #[test]
+    fn handles_json_answers() {
+        let json =
+            r#"{ "data": [{ "day": "01", "part": 2, "answer": "123", "verdict": "too_low" }] }"#
+                .to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.data.len(), 1);
+        let answer = answers.data.first().unwrap();
+        assert_eq!(answer.day, day!(1));
+        assert_eq!(answer.part, 2);
+        assert_eq!(answer.answer, "123");
+        assert_eq!(answer.verdict, Verdict::TooLow);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json =
+            r#"{ "data": [{ "day": "01", "part": 3, "answer": "1", "verdict": "correct" }] }"#
+                .to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let json = tinyjson::JsonValue::from(get_mock_answers())
+            .stringify()
+            .unwrap();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.data.len(), 5);
+        assert_eq!(answers.correct(day!(1), 2), Some("7"));
+    }
+
+    #[test]
+    fn looks_up_verdicts() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.verdict(day!(1), 1, "42"), Some(Verdict::Incorrect));
+        assert_eq!(answers.verdict(day!(1), 2, "42"), None);
+        assert_eq!(answers.correct(day!(1), 1), None);
+    }
+
+    #[test]
+    fn records_answers() {
+        let mut answers = get_mock_answers();
+        answers.record(day!(1), 1, "42", Verdict::Throttled);
+        answers.record(day!(1), 1, "30", Verdict::Correct);
+        assert_eq!(answers.data.len(), 6);
+        assert_eq!(answers.verdict(day!(1), 1, "42"), Some(Verdict::Throttled));
+        assert_eq!(answers.correct(day!(1), 1), Some("30"));
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.bound_violation(day!(1), 1, "30"), None);
+        assert_eq!(
+            answers.bound_violation(day!(1), 1, "70"),
+            Some("70 is not below 50, which is too high".into())
+        );
+        assert_eq!(
+            answers.bound_violation(day!(1), 1, "10"),
+            Some("10 is not above 10, which is too low".into())
+        );
+        assert_eq!(answers.bound_violation(day!(1), 1, "abc"), None);
+        assert_eq!(answers.bound_violation(day!(1), 2, "1000"), None);
+    }
+}
@@ -0,0 +1,565 @@
+/// Minimal client for the Advent of Code website.
+/// Authenticates with the session cookie of a logged-in browser, see `find_session` for where it is looked up.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::{workspace_path, Puzzle};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+static USER_AGENT: &str = concat!(
+    "advent_of_code/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/Cormochamelion/advent-of-code-2024)"
+);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    BadStatus(u16),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or write it to \"~/.adventofcode.session\"."
+            ),
+            AocClientError::BadStatus(400 | 500) => write!(
+                f,
+                "the server rejected the request, the session cookie might be invalid or expired."
+            ),
+            AocClientError::BadStatus(404) => {
+                write!(f, "the puzzle could not be found, it might not be unlocked yet.")
+            }
+            AocClientError::BadStatus(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "the request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// Outcome of an answer submission, as reported by the website.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    Throttled,
+    AlreadySolved,
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Throttled => "throttled",
+            Verdict::AlreadySolved => "already solved",
+            Verdict::Unknown => "unknown",
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub verdict: Verdict,
+    pub message: String,
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Creates a client from the environment.
+    /// The base URL can be overridden with `AOC_BASE_URL`, e.g. to point at a local server.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = find_session().ok_or(AocClientError::SessionNotFound)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    pub fn fetch_input(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.puzzle_url(puzzle));
+        self.get(&url)
+    }
+
+    /// Fetches the puzzle description and converts it to markdown.
+    pub fn fetch_puzzle(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let html = self.get(&self.puzzle_url(puzzle))?;
+        Ok(html::description_to_markdown(&html))
+    }
+
+    pub fn submit(
+        &self,
+        puzzle: Puzzle,
+        part: u8,
+        result: &str,
+    ) -> Result<Submission, AocClientError> {
+        let url = format!("{}/answer", self.puzzle_url(puzzle));
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", result)])?
+            .into_string()?;
+
+        let message = html::article_text(&html);
+        Ok(Submission {
+            verdict: parse_verdict(&message),
+            message,
+        })
+    }
+
+    fn puzzle_url(&self, puzzle: Puzzle) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let body = self
+            .agent
+            .get(url)
+            .set("Cookie", &self.cookie())
+            .call()?
+            .into_string()?;
+        Ok(body)
+    }
+}
+
+pub fn check() -> Result<(), AocClientError> {
+    AocClient::from_env().map(|_| ())
+}
+
+pub fn read(puzzle: Puzzle) -> Result<String, AocClientError> {
+    let puzzle_path = workspace_path(puzzle.data_path("puzzles", "md"));
+
+    let description = AocClient::from_env()?.fetch_puzzle(puzzle)?;
+    write_data_file(&puzzle_path, &description)?;
+    println!("{description}");
+    Ok(description)
+}
+
+pub fn download(puzzle: Puzzle) -> Result<(), AocClientError> {
+    let input_path = workspace_path(puzzle.data_path("inputs", "txt"));
+    let puzzle_path = workspace_path(puzzle.data_path("puzzles", "md"));
+
+    let client = AocClient::from_env()?;
+    let input = client.fetch_input(puzzle)?;
+    let description = client.fetch_puzzle(puzzle)?;
+
+    write_data_file(&input_path, &input)?;
+    write_data_file(&puzzle_path, &description)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+pub fn submit(puzzle: Puzzle, part: u8, result: &str) -> Result<Submission, AocClientError> {
+    AocClient::from_env()?.submit(puzzle, part, result)
+}
+
+/// Writes a data file, creating the folders of a year that has no data yet.
+fn write_data_file(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Looks up the session cookie in `AOC_SESSION`, then in the session files also used by `aoc-cli`.
+fn find_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+
+    [
+        home.map(|home| home.join(".adventofcode.session")),
+        config_dir.map(|dir| dir.join("adventofcode.session")),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|path| fs::read_to_string(path).ok())
+    .map(|session| session.trim().to_string())
+    .filter(|session| !session.is_empty())
+}
+
+fn parse_verdict(message: &str) -> Verdict {
+    if message.contains("That's the right answer") {
+        Verdict::Correct
+    } else if message.contains("You gave an answer too recently") {
+        Verdict::Throttled
+    } else if message.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else if message.contains("That's not the right answer") {
+        if message.contains("too high") {
+            Verdict::TooHigh
+        } else if message.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        }
+    } else {
+        Verdict::Unknown
+    }
+}
+
+/// Just enough HTML handling to turn puzzle pages into readable markdown.
+mod html {
+    /// Converts every `<article class="day-desc">` of a puzzle page to markdown.
+    pub fn description_to_markdown(html: &str) -> String {
+        articles(html)
+            .map(to_markdown)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim_end()
+            .to_string()
+            + "\n"
+    }
+
+    /// Extracts the text of the first `<article>` of a page, e.g. the message after submitting an answer.
+    pub fn article_text(html: &str) -> String {
+        let article = articles(html).next().unwrap_or(html);
+        let mut text = String::new();
+        for token in tokens(article) {
+            if let Token::Text(s) = token {
+                text.push_str(&decode_entities(s));
+            }
+        }
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn articles(html: &str) -> impl Iterator<Item = &str> {
+        html.split("<article").skip(1).filter_map(|s| {
+            let start = s.find('>')? + 1;
+            let end = s.find("</article>")?;
+            s.get(start..end)
+        })
+    }
+
+    enum Token<'a> {
+        Open(&'a str, &'a str),
+        Close(&'a str),
+        Text(&'a str),
+    }
+
+    fn tokens(html: &str) -> impl Iterator<Item = Token<'_>> {
+        let mut rest = html;
+        std::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+
+            if let Some(tag) = rest.strip_prefix('<') {
+                let end = tag.find('>').unwrap_or(tag.len());
+                let inner = &tag[..end];
+                rest = tag.get(end + 1..).unwrap_or_default();
+
+                Some(match inner.strip_prefix('/') {
+                    Some(name) => Token::Close(name.trim()),
+                    None => {
+                        let (name, attrs) = inner.split_once(' ').unwrap_or((inner, ""));
+                        Token::Open(name.trim_end_matches('/'), attrs)
+                    }
+                })
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                let text = &rest[..end];
+                rest = &rest[end..];
+                Some(Token::Text(text))
+            }
+        })
+    }
+
+    fn to_markdown(html: &str) -> String {
+        let mut md = String::new();
+        let mut in_pre = false;
+        let mut links: Vec<String> = vec![];
+
+        for token in tokens(html) {
+            match token {
+                Token::Open("h2", _) => md.push_str("## "),
+                Token::Close("h2" | "p") => md.push_str("\n\n"),
+                Token::Open("pre", _) => {
+                    md.push_str("```\n");
+                    in_pre = true;
+                }
+                Token::Close("pre") => {
+                    if !md.ends_with('\n') {
+                        md.push('\n');
+                    }
+                    md.push_str("```\n\n");
+                    in_pre = false;
+                }
+                Token::Open("code", _) | Token::Close("code") if !in_pre => md.push('`'),
+                Token::Open("em", _) | Token::Close("em") if !in_pre => md.push('*'),
+                Token::Open("li", _) => md.push_str("- "),
+                Token::Close("li" | "ul") => md.push('\n'),
+                Token::Open("a", attrs) => {
+                    md.push('[');
+                    links.push(attribute(attrs, "href").unwrap_or_default().to_string());
+                }
+                Token::Close("a") => {
+                    let href = links.pop().unwrap_or_default();
+                    md.push_str(&format!("]({href})"));
+                }
+                // skip whitespace between block elements.
+                Token::Text(s) if !in_pre && s.trim().is_empty() => {}
+                Token::Text(s) => md.push_str(&decode_entities(s)),
+                _ => {}
+            }
+        }
+
+        md
+    }
+
+    fn attribute<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+        let start = attrs.find(&format!("{name}=\""))? + name.len() + 2;
+        let len = attrs[start..].find('"')?;
+        Some(&attrs[start..start + len])
+    }
+
+    fn decode_entities(s: &str) -> String {
+        s.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&")
+    }
+
+    #[cfg(feature = "test_lib")]
+    mod tests {
+        use super::{article_text, description_to_markdown};
+
+        #[test]
+        fn converts_description_to_markdown() {
+            let html = r#"<main><article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Read <a href="/about">this</a> &amp; that:</p>
+<pre><code>1 2
+3 &lt; 4
+</code></pre>
+<ul><li>The answer is <code><em>11</em></code>.</li></ul>
+</article></main>"#;
+
+            let expected = [
+                "## --- Day 1: Test ---",
+                "",
+                "Read [this](/about) & that:",
+                "",
+                "```",
+                "1 2",
+                "3 < 4",
+                "```",
+                "",
+                "- The answer is `*11*`.",
+                "",
+            ]
+            .join("\n");
+
+            assert_eq!(description_to_markdown(html), expected);
+        }
+
+        #[test]
+        fn extracts_article_text() {
+            let html = "<html><main><article><p>That's not the right answer; your answer is <em>too high</em>.</p>\n</article></main></html>";
+            assert_eq!(
+                article_text(html),
+                "That's not the right answer; your answer is too high."
+            );
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    use super::{AocClient, AocClientError, Verdict};
+    use crate::{day, template::Puzzle, year};
+
+    /// Serves a single canned response on a random local port, returning its URL and the raw request.
+    fn mock_server(status: u16, body: &'static str) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut form = vec![0; content_length];
+            reader.read_exact(&mut form).unwrap();
+            request.push_str(&String::from_utf8(form).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            tx.send(request).unwrap();
+        });
+
+        (url, rx)
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (url, request) = mock_server(200, "1 2 3\n");
+        let client = AocClient::new(&url, "abc");
+
+        assert_eq!(
+            client
+                .fetch_input(Puzzle::new(year!(2024), day!(5)))
+                .unwrap(),
+            "1 2 3\n"
+        );
+
+        let request = request.recv().unwrap();
+        assert!(request.starts_with("GET /2024/day/5/input HTTP/1.1"));
+        assert!(request.contains("session=abc"));
+    }
+
+    #[test]
+    fn maps_bad_status() {
+        let (url, _request) = mock_server(400, "");
+        let client = AocClient::new(&url, "abc");
+
+        assert!(matches!(
+            client.fetch_input(Puzzle::new(year!(2024), day!(5))),
+            Err(AocClientError::BadStatus(400))
+        ));
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (url, request) = mock_server(
+            200,
+            "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>",
+        );
+        let client = AocClient::new(&url, "abc");
+
+        let submission = client
+            .submit(Puzzle::new(year!(2024), day!(12)), 2, "1234")
+            .unwrap();
+        assert_eq!(submission.verdict, Verdict::Correct);
+
+        let request = request.recv().unwrap();
+        assert!(request.starts_with("POST /2024/day/12/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=1234"));
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        use super::parse_verdict;
+
+        assert_eq!(
+            parse_verdict("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            parse_verdict("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            parse_verdict("That's not the right answer. If you're stuck, ..."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            parse_verdict("You gave an answer too recently; you have to wait after submitting an answer before trying again."),
+            Verdict::Throttled
+        );
+        assert_eq!(
+            parse_verdict(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            Verdict::AlreadySolved
+        );
+    }
+}
@@ -1,5 +1,22 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::output::OutputFormat;
+use crate::template::registry::Solution;
+use crate::template::{all_days, run_multi::run_multi, Year};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+/// Runs all days of a year. Registered solutions run in this process, unless `jobs` asks for several solution bins at once.
+pub fn handle(
+    year: Year,
+    is_release: bool,
+    jobs: Option<usize>,
+    solutions: &[Solution],
+    format: OutputFormat,
+) {
+    run_multi(
+        year,
+        &all_days(year).collect(),
+        is_release,
+        false,
+        jobs.unwrap_or(1),
+        solutions,
+        format,
+    );
 }
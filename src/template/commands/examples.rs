@@ -0,0 +1,117 @@
+use std::{fs, path::Path, process};
+
+use crate::template::examples::{
+    extract_from_description, fill_in_scaffolded_test, Example, Manifest,
+};
+use crate::template::{workspace_path, Puzzle};
+
+/// Extracts the example inputs and answers from the puzzle description that `download` or `read` stored.
+/// The inputs are written to the example files, the answers to the manifest of the day and the tests `scaffold` created.
+/// Files that are not empty are only replaced with `overwrite`.
+pub fn extract(puzzle: Puzzle, overwrite: bool) {
+    let description_path = workspace_path(puzzle.data_path("puzzles", "md"));
+    let Ok(description) = fs::read_to_string(&description_path) else {
+        eprintln!(
+            "Could not read \"{}\", run `download` or `read` first.",
+            description_path.display()
+        );
+        process::exit(1);
+    };
+
+    let examples_dir = workspace_path(puzzle.year.data_dir().join("examples"));
+    if let Err(e) = fs::create_dir_all(&examples_dir) {
+        eprintln!("Failed to create \"{}\": {e}", examples_dir.display());
+        process::exit(1);
+    }
+
+    let bin_path = workspace_path(puzzle.bin_path());
+    let bin_source = fs::read_to_string(&bin_path).ok();
+    let mut filled_source = bin_source.clone();
+    let mut manifest = Manifest::default();
+
+    for example in extract_from_description(&description) {
+        // the second part uses the example of the first one, unless it brings its own.
+        let file_part = (example.part == 2 && example.input.is_some()).then_some(2);
+        let file = match file_part {
+            Some(part) => format!("{}-{part}.txt", puzzle.day),
+            None => format!("{}.txt", puzzle.day),
+        };
+
+        if let Some(input) = &example.input {
+            write_unless_filled(&examples_dir.join(&file), input, overwrite);
+        }
+
+        let Some(answer) = example.answer else {
+            println!("No example answer found for part {}.", example.part);
+            continue;
+        };
+
+        let test = if example.part == 1 {
+            "test_part_one"
+        } else {
+            "test_part_two"
+        };
+        if let Some(source) = filled_source
+            .as_deref()
+            .and_then(|source| fill_in_scaffolded_test(source, test, &answer, file_part))
+        {
+            println!("Filled in `{test}` with {answer}.");
+            filled_source = Some(source);
+        }
+
+        manifest.examples.push(Example {
+            name: None,
+            file,
+            part: example.part,
+            answer,
+        });
+    }
+
+    if filled_source != bin_source {
+        if let Err(e) = fs::write(&bin_path, filled_source.unwrap_or_default()) {
+            eprintln!("Failed to write \"{}\": {e}", bin_path.display());
+            process::exit(1);
+        }
+    }
+
+    if manifest.examples.is_empty() {
+        return;
+    }
+
+    let manifest_path = workspace_path(puzzle.data_path("examples", "json"));
+    let has_manifest = Manifest::read_from_file(puzzle).is_ok_and(|m| !m.examples.is_empty());
+    if has_manifest && !overwrite {
+        println!(
+            "Skipped \"{}\", it already lists examples. Pass `--overwrite` to replace it.",
+            manifest_path.display()
+        );
+        return;
+    }
+
+    match manifest.store_file(puzzle) {
+        Ok(()) => println!("Wrote example answers to \"{}\".", manifest_path.display()),
+        Err(e) => {
+            eprintln!("Failed to write \"{}\": {e}", manifest_path.display());
+            process::exit(1);
+        }
+    }
+}
+
+fn write_unless_filled(path: &Path, contents: &str, overwrite: bool) {
+    let is_filled = fs::read_to_string(path).is_ok_and(|s| !s.trim().is_empty());
+    if is_filled && !overwrite {
+        println!(
+            "Skipped \"{}\", it is not empty. Pass `--overwrite` to replace it.",
+            path.display()
+        );
+        return;
+    }
+
+    match fs::write(path, contents) {
+        Ok(()) => println!("Wrote example input to \"{}\".", path.display()),
+        Err(e) => {
+            eprintln!("Failed to write \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+    }
+}
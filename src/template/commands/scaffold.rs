@@ -1,79 +1,220 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::PathBuf,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{workspace_path, Puzzle, Year};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// The folder that templates are looked up in by name, see its `README.md`.
+const TEMPLATES_DIR: &str = "templates";
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
+/// The template used unless `--template` picks another one.
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// The arguments of `solution!` for a puzzle. Solutions of the configured year leave out their year.
+fn solution_args(puzzle: Puzzle) -> String {
+    let day = puzzle.day.into_inner();
+    if Year::from_env() == Some(puzzle.year) {
+        day.to_string()
     } else {
-        file.create_new(true);
+        format!("{day}, year = {}", puzzle.year)
     }
-    file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Resolves a template name like `grid` to `templates/grid.txt` in the workspace. Anything that looks like a path is used as is.
+fn template_path(template: &str) -> PathBuf {
+    if template.contains(['/', '\\']) || template.ends_with(".txt") {
+        PathBuf::from(template)
+    } else {
+        workspace_path(TEMPLATES_DIR).join(format!("{template}.txt"))
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Replaces the placeholders of a template with the values of a puzzle.
+fn render(template: &str, puzzle: Puzzle) -> String {
+    template
+        .replace("%SOLUTION_ARGS%", &solution_args(puzzle))
+        .replace("%DAY_NUMBER%", &puzzle.day.into_inner().to_string())
+        .replace("%DAY%", &puzzle.day.to_string())
+        .replace("%YEAR%", &puzzle.year.to_string())
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+/// What `scaffold` does with one of the files of a puzzle.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Create,
+    Overwrite,
+    Keep,
+    /// The file exists and may not be replaced, so nothing is written at all.
+    Conflict,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Self::Create => "create",
+            Self::Overwrite => "overwrite",
+            Self::Keep => "keep",
+            Self::Conflict => "exists",
+        })
+    }
+}
+
+/// A file operation that is planned before anything is written, so that a failing step can be undone.
+struct FileOp {
+    path: PathBuf,
+    contents: String,
+    /// The contents of the file before scaffolding, if it exists.
+    previous: Option<String>,
+    action: Action,
+}
+
+impl FileOp {
+    /// Plans to write a file. Existing files are only replaced with `replace`, or when they are empty.
+    fn plan(path: PathBuf, contents: String, replace: bool) -> Result<Self, String> {
+        let previous = match fs::read_to_string(&path) {
+            Ok(previous) => Some(previous),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(format!("Failed to read \"{}\": {e}", path.display())),
+        };
+
+        let action = match &previous {
+            None => Action::Create,
+            Some(previous) if *previous == contents => Action::Keep,
+            Some(_) if replace => Action::Overwrite,
+            // input and example files are scaffolded empty, existing ones are left as they are.
+            Some(_) if contents.is_empty() => Action::Keep,
+            Some(_) => Action::Conflict,
+        };
+
+        Ok(Self {
+            path,
+            contents,
+            previous,
+            action,
+        })
+    }
+
+    fn describe(&self) -> String {
+        let mut line = format!("  {:<9} {}", self.action, self.path.display());
+        if let Some(previous) = self.previous.as_ref().filter(|p| !p.is_empty()) {
+            line.push_str(&format!(" ({} bytes)", previous.len()));
         }
-    };
+        line
+    }
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+    fn apply(&self) -> Result<(), std::io::Error> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+        match self.action {
+            Action::Create => OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&self.path)?
+                .write_all(self.contents.as_bytes()),
+            Action::Overwrite => fs::write(&self.path, &self.contents),
+            Action::Keep | Action::Conflict => Ok(()),
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    fn undo(&self) -> Result<(), std::io::Error> {
+        match (self.action, &self.previous) {
+            (Action::Create, _) => fs::remove_file(&self.path),
+            (Action::Overwrite, Some(previous)) => fs::write(&self.path, previous),
+            _ => Ok(()),
         }
     }
+}
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+/// Plans the module, input and example file of a puzzle.
+/// The module is replaced with `overwrite`, input and example files that are not empty only with `force`.
+fn plan(
+    puzzle: Puzzle,
+    template: &str,
+    overwrite: bool,
+    force: bool,
+) -> Result<Vec<FileOp>, String> {
+    let template_path = template_path(template);
+    let module_template = fs::read_to_string(&template_path).map_err(|e| {
+        format!(
+            "Failed to read template \"{}\": {e}",
+            template_path.display()
+        )
+    })?;
+
+    Ok(vec![
+        FileOp::plan(
+            workspace_path(puzzle.bin_path()),
+            render(&module_template, puzzle),
+            overwrite,
+        )?,
+        FileOp::plan(
+            workspace_path(puzzle.data_path("inputs", "txt")),
+            String::new(),
+            force,
+        )?,
+        FileOp::plan(
+            workspace_path(puzzle.data_path("examples", "txt")),
+            String::new(),
+            force,
+        )?,
+    ])
+}
+
+/// Runs the planned operations in order. If one fails, the ones before it are undone.
+fn apply(ops: &[FileOp]) -> Result<(), String> {
+    for (i, op) in ops.iter().enumerate() {
+        if let Err(e) = op.apply() {
+            for done in ops[..i].iter().rev() {
+                if let Err(e) = done.undo() {
+                    eprintln!("Failed to roll back \"{}\": {e}", done.path.display());
+                }
+            }
+            return Err(format!(
+                "Failed to write \"{}\": {e}, the files written before it were rolled back.",
+                op.path.display()
+            ));
         }
+    }
+    Ok(())
+}
+
+pub fn handle(puzzle: Puzzle, template: &str, overwrite: bool, force: bool) {
+    let ops = match plan(puzzle, template, overwrite, force) {
+        Ok(ops) => ops,
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("{e}");
             process::exit(1);
         }
+    };
+
+    println!("Scaffolding {puzzle}:");
+    for op in &ops {
+        println!("{}", op.describe());
+    }
+
+    if ops.iter().any(|op| op.action == Action::Conflict) {
+        eprintln!("---");
+        eprintln!("Nothing was changed. Pass `--overwrite` to replace the module file.");
+        process::exit(1);
+    }
+
+    if let Err(e) = apply(&ops) {
+        eprintln!("{e}");
+        process::exit(1);
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if Year::from_env() == Some(puzzle.year) {
+        println!("🎄 Type `cargo solve {}` to run your solution.", puzzle.day);
+    } else {
+        println!(
+            "🎄 Type `cargo solve {} --year {}` to run your solution.",
+            puzzle.day, puzzle.year
+        );
+    }
 }
@@ -1,9 +1,18 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::input::InputSource;
+use crate::template::output::OutputFormat;
+use crate::template::Puzzle;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    puzzle: Puzzle,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    format: OutputFormat,
+    input: &InputSource,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -18,6 +27,13 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
+    if !format.is_text() {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.as_arg().to_string());
+    }
+
+    cmd_args.extend(input.as_args());
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
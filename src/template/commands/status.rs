@@ -0,0 +1,141 @@
+use std::{fs, panic, path::Path};
+
+use crate::template::answers::Answers;
+use crate::template::examples::Manifest;
+use crate::template::output::OutputFormat;
+use crate::template::registry::{self, Solution};
+use crate::template::run_multi::{run_in_process, InProcessError};
+use crate::template::runner::{RunOptions, PARSE_STEP};
+use crate::template::table;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{all_days, workspace_path, Day, Puzzle, Year};
+
+/// Prints where every day of a year stands: which files exist, what the parts return and how they were judged.
+/// Registered solutions with an input are run once in this process to get their answers.
+pub fn handle(year: Year, solutions: &[Solution]) {
+    let answers = Answers::read_from_file(year);
+    let timings = Timings::read_from_file(year);
+
+    // solutions that are not done yet tend to panic, their messages would break up the table.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let rows: Vec<Vec<String>> = all_days(year)
+        .map(|day| {
+            let puzzle = Puzzle::new(year, day);
+            let results = registry::find(solutions, puzzle)
+                .filter(|_| is_filled(&workspace_path(puzzle.data_path("inputs", "txt"))))
+                .map(|solution| {
+                    let options = RunOptions {
+                        is_timed: false,
+                        format: OutputFormat::Json,
+                    };
+                    run_in_process(solution, options)
+                });
+
+            let part = |part: u8| {
+                if part == 2 && day == year.last_day() {
+                    return "-".to_string();
+                }
+                match &results {
+                    None => "-".into(),
+                    Some(Err(InProcessError::MissingInput(_))) => "missing input".into(),
+                    Some(Err(InProcessError::Panicked)) => "panicked".into(),
+                    Some(Ok(results)) => {
+                        let answer = results
+                            .iter()
+                            .find(|r| r.part == part)
+                            .and_then(|r| r.answer.as_deref());
+                        describe_answer(&answers, day, part, answer)
+                    }
+                }
+            };
+
+            vec![
+                day.to_string(),
+                check(workspace_path(puzzle.bin_path()).exists()),
+                describe_input(&workspace_path(puzzle.data_path("inputs", "txt"))),
+                check(workspace_path(puzzle.data_path("puzzles", "md")).exists()),
+                describe_examples(puzzle),
+                part(1),
+                part(2),
+                timings
+                    .data
+                    .iter()
+                    .find(|t| t.day == day)
+                    .map_or_else(|| "-".into(), total_time),
+            ]
+        })
+        .collect();
+
+    panic::set_hook(hook);
+
+    table::print_table(
+        &[
+            "Day",
+            "Module",
+            "Input",
+            "Puzzle",
+            "Examples",
+            "Part 1",
+            "Part 2",
+            "Benchmark",
+        ],
+        &rows,
+    );
+
+    let stars = all_days(year)
+        .flat_map(|day| (1..=2).map(move |part| (day, part)))
+        .filter(|(day, part)| answers.correct(*day, *part).is_some())
+        .count();
+    println!("\n{stars} parts of {year} answered correctly.");
+}
+
+fn check(exists: bool) -> String {
+    if exists { "✔" } else { "-" }.into()
+}
+
+fn is_filled(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|s| !s.trim().is_empty())
+}
+
+fn describe_input(path: &Path) -> String {
+    match fs::read_to_string(path) {
+        Ok(input) if input.trim().is_empty() => "empty".into(),
+        Ok(_) => "✔".into(),
+        Err(_) => "-".into(),
+    }
+}
+
+/// Whether the example file has contents, and how many answers the manifest of the day lists.
+fn describe_examples(puzzle: Puzzle) -> String {
+    let has_example = is_filled(&workspace_path(puzzle.data_path("examples", "txt")));
+    let checked = Manifest::read_from_file(puzzle).map_or(0, |m| m.examples.len());
+
+    match (has_example, checked) {
+        (false, 0) => "-".into(),
+        (true, 0) => "✔".into(),
+        (_, checked) => format!("✔ {checked} checked"),
+    }
+}
+
+/// The answer a part returned, together with the verdict it got when it was submitted.
+fn describe_answer(answers: &Answers, day: Day, part: u8, answer: Option<&str>) -> String {
+    let Some(answer) = answer else {
+        return "None".into();
+    };
+
+    match answers.verdict(day, part, answer) {
+        Some(verdict) => format!("{answer} ({verdict})"),
+        None if answers.correct(day, part).is_some() => format!("{answer} (not the correct one)"),
+        None => format!("{answer} (not submitted)"),
+    }
+}
+
+fn total_time(timing: &Timing) -> String {
+    let total = [PARSE_STEP, 1, 2]
+        .into_iter()
+        .filter_map(|part| timing.part_time(part))
+        .sum::<std::time::Duration>();
+    format!("{total:.1?}")
+}
@@ -1,35 +1,102 @@
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    process,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
+use crate::template::compare_timings::{compare, print_deltas};
+use crate::template::output::OutputFormat;
+use crate::template::registry::Solution;
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::table::print_table;
+use crate::template::timings::{HistoryEntry, Timings};
+use crate::template::{all_days, git, readme_benchmarks, Day, Year};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Benchmarks days of a year one at a time, unless `jobs` is set, to keep the timings stable.
+/// With a `compare_threshold`, the stored timings serve as a baseline and the command fails
+/// if any part got slower by more than the threshold (in percent). Regressed timings are not stored.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    jobs: Option<usize>,
+    compare_threshold: Option<f64>,
+    solutions: &[Solution],
+    format: OutputFormat,
+) {
+    let mut stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
-                all_days().collect()
+            // comparing needs the days that are already benched, too.
+            if run_all || compare_threshold.is_some() {
+                all_days(year).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                all_days(year)
+                    .filter(|day| !stored_timings.is_day_complete(year, *day))
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(
+        year,
+        &days_to_run,
+        true,
+        true,
+        jobs.unwrap_or(1),
+        solutions,
+        format,
+    )
+    .unwrap();
+
+    // the history keeps every run, whether or not its timings are stored.
+    if !timings.data.is_empty() {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        stored_timings.record_history(&timings, timestamp, git::head_commit().as_deref());
+
+        if let Err(e) = stored_timings.store_file(year) {
+            eprintln!("Failed to store timing history: {e}");
+        }
+    }
+
+    if let Some(threshold) = compare_threshold {
+        let deltas = compare(&stored_timings, &timings);
+
+        // keep machine-readable output on stdout clean.
+        if format.is_text() {
+            println!();
+            print_deltas(&deltas, threshold);
+        }
+
+        let regressions = deltas.iter().filter(|d| d.is_regression(threshold)).count();
+        if regressions > 0 {
+            eprintln!("\n{regressions} part(s) regressed by more than {threshold}%.");
+            process::exit(1);
+        }
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
+
+        // the readme only lists the benchmarks of the configured year.
+        if Year::from_env() != Some(year) {
+            return;
+        }
 
-        println!();
         match readme_benchmarks::update(merged_timings) {
+            // keep machine-readable output on stdout clean.
+            Ok(()) if !format.is_text() => eprintln!("Stored updated benchmarks."),
             Ok(()) => {
+                println!();
                 println!("Stored updated benchmarks.");
             }
             Err(_) => {
@@ -38,3 +105,95 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Print how the runtime of a day evolved over its recorded benchmark runs.
+pub fn history(year: Year, day: Day) {
+    let timings = Timings::read_from_file(year);
+    let entries: Vec<&HistoryEntry> = timings.day_history(day).collect();
+
+    if entries.is_empty() {
+        println!("No benchmark runs recorded for day {day}.");
+        return;
+    }
+
+    let total = |entry: &HistoryEntry| {
+        let nanos = [entry.parse_nanos, entry.part_1_nanos, entry.part_2_nanos]
+            .iter()
+            .flatten()
+            .sum::<f64>();
+        (nanos > 0.0).then_some(nanos)
+    };
+
+    let rows: Vec<Vec<String>> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let change = match (
+                i.checked_sub(1).and_then(|i| total(entries[i])),
+                total(entry),
+            ) {
+                (Some(previous), Some(current)) => {
+                    format!("{:+.1}%", (current - previous) / previous * 100.0)
+                }
+                _ => "-".into(),
+            };
+
+            vec![
+                format_timestamp(entry.timestamp),
+                entry
+                    .commit
+                    .as_ref()
+                    .map_or_else(|| "-".into(), |c| c.chars().take(7).collect()),
+                format_nanos(entry.parse_nanos),
+                format_nanos(entry.part_1_nanos),
+                format_nanos(entry.part_2_nanos),
+                format_nanos(total(entry)),
+                change,
+            ]
+        })
+        .collect();
+
+    print_table(
+        &[
+            "Date (UTC)",
+            "Commit",
+            "Parse",
+            "Part 1",
+            "Part 2",
+            "Total",
+            "Change",
+        ],
+        &rows,
+    );
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: Option<f64>) -> String {
+    nanos.map_or_else(
+        || "-".into(),
+        |n| format!("{:.1?}", Duration::from_nanos(n as u64)),
+    )
+}
+
+/// Formats seconds since the unix epoch as `YYYY-MM-DD HH:MM` in UTC.
+#[allow(clippy::cast_possible_wrap)]
+fn format_timestamp(timestamp: u64) -> String {
+    let (days, seconds) = ((timestamp / 86400) as i64, timestamp % 86400);
+
+    // civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
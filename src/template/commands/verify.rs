@@ -0,0 +1,113 @@
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::output::OutputFormat;
+use crate::template::run_multi::child_commands::run_solution;
+use crate::template::runner::emit_part;
+use crate::template::table;
+use crate::template::{all_days, Day, Puzzle, Year, ANSI_BOLD, ANSI_RESET};
+
+struct Check {
+    day: Day,
+    part: u8,
+    expected: String,
+    actual: Option<String>,
+    /// Set if the solution of the day could not be run, or the part failed with an error.
+    error: Option<String>,
+}
+
+impl Check {
+    fn passed(&self) -> bool {
+        self.error.is_none() && self.actual.as_ref() == Some(&self.expected)
+    }
+
+    fn status(&self) -> &'static str {
+        match (&self.error, self.passed()) {
+            (Some(_), _) => "✖ error",
+            (None, true) => "✔ pass",
+            (None, false) => "✖ fail",
+        }
+    }
+}
+
+/// Re-run every day of a year with a known correct answer and compare the results against the answer ledger.
+pub fn handle(year: Year, day: Option<Day>, is_release: bool) {
+    let answers = Answers::read_from_file(year);
+
+    let days_to_verify: Vec<Day> = all_days(year)
+        .filter(|d| day.is_none_or(|day| day == *d))
+        .filter(|d| (1..=2).any(|part| answers.correct(*d, part).is_some()))
+        .collect();
+
+    if days_to_verify.is_empty() {
+        println!("No correct answers recorded, nothing to verify.");
+        return;
+    }
+
+    let mut checks: Vec<Check> = vec![];
+
+    for day in days_to_verify {
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        // a day that cannot be run fails its checks, the other days are still verified.
+        let (records, error) = match run_solution(Puzzle::new(year, day), false, is_release) {
+            Ok(records) => (records, None),
+            Err(e) => {
+                eprintln!("Failed to run day {day}: {e}");
+                (vec![], Some(e.to_string()))
+            }
+        };
+        records
+            .iter()
+            .for_each(|r| emit_part(r, OutputFormat::Text));
+        println!();
+
+        for part in 1..=2 {
+            if let Some(expected) = answers.correct(day, part) {
+                let record = records.iter().find(|r| r.part == part);
+                checks.push(Check {
+                    day,
+                    part,
+                    expected: expected.into(),
+                    actual: record.and_then(|r| r.answer.clone()),
+                    error: error
+                        .clone()
+                        .or_else(|| record.and_then(|r| r.error.clone())),
+                });
+            }
+        }
+    }
+
+    print_table(&checks);
+
+    let failed = checks.iter().filter(|c| !c.passed()).count();
+    if failed > 0 {
+        eprintln!("\n{failed} of {} checks failed.", checks.len());
+        process::exit(1);
+    }
+
+    println!("\nAll {} checks passed.", checks.len());
+}
+
+fn print_table(checks: &[Check]) {
+    let rows: Vec<Vec<String>> = checks
+        .iter()
+        .map(|c| {
+            vec![
+                c.day.to_string(),
+                c.part.to_string(),
+                c.expected.clone(),
+                // the causes of an error are on lines of their own, which a cell cannot hold.
+                c.error
+                    .as_ref()
+                    .map(|e| e.lines().collect::<Vec<_>>().join(", "))
+                    .or_else(|| c.actual.clone())
+                    .unwrap_or_else(|| "-".into()),
+                c.status().into(),
+            ]
+        })
+        .collect();
+
+    table::print_table(&["Day", "Part", "Expected", "Actual", "Status"], &rows);
+}
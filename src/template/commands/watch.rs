@@ -0,0 +1,141 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::input::InputSource;
+use crate::template::output::OutputFormat;
+use crate::template::run_multi::child_commands::capture_solution;
+use crate::template::runner::PARSE_STEP;
+use crate::template::{workspace_path, Puzzle, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Re-runs a day on `input`, or its tests with `test`, whenever its module, examples or input change.
+/// Changes are found by polling modification times, so this runs until it is interrupted.
+pub fn handle(puzzle: Puzzle, release: bool, test: bool, input: &InputSource) {
+    let mut last_answers: HashMap<u8, Option<String>> = HashMap::new();
+    let mut last_seen = HashMap::new();
+
+    loop {
+        let seen = modified_times(puzzle, input);
+        if seen == last_seen {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+        // files that change while the day runs trigger another run.
+        last_seen = seen;
+
+        print!("{ANSI_CLEAR_SCREEN}");
+        println!("{ANSI_BOLD}Watching {puzzle}{ANSI_RESET}, press Ctrl-C to stop.");
+        println!("---");
+
+        if test {
+            run_tests(puzzle, release);
+        } else {
+            run_day(puzzle, release, input, &mut last_answers);
+        }
+    }
+}
+
+/// The files that trigger a re-run: the module, every example file of the day and the input.
+fn watched_files(puzzle: Puzzle, input: &InputSource) -> Vec<PathBuf> {
+    let mut files = vec![
+        workspace_path(puzzle.bin_path()),
+        workspace_path(puzzle.data_path("inputs", "txt")),
+    ];
+    if let InputSource::File(path) = input {
+        files.push(path.clone());
+    }
+
+    let examples_dir = workspace_path(puzzle.year.data_dir().join("examples"));
+    if let Ok(entries) = fs::read_dir(&examples_dir) {
+        let prefix = puzzle.day.to_string();
+        files.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".txt"))
+                }),
+        );
+    }
+
+    files
+}
+
+fn modified_times(puzzle: Puzzle, input: &InputSource) -> HashMap<PathBuf, Option<SystemTime>> {
+    watched_files(puzzle, input)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+fn run_day(
+    puzzle: Puzzle,
+    release: bool,
+    input: &InputSource,
+    last_answers: &mut HashMap<u8, Option<String>>,
+) {
+    let output = match capture_solution(puzzle, false, release, input) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run {puzzle}: {e}");
+            return;
+        }
+    };
+
+    output.replay(OutputFormat::Text);
+
+    if output.records().next().is_none() {
+        println!("Not solved.");
+        return;
+    }
+
+    let mut changes = vec![];
+    for record in output.records() {
+        if record.part == PARSE_STEP {
+            continue;
+        }
+
+        if let Some(last) = last_answers.insert(record.part, record.answer.clone()) {
+            if last != record.answer {
+                changes.push(format!(
+                    "Part {}: {} → {}",
+                    record.part,
+                    last.as_deref().unwrap_or("None"),
+                    record.answer.as_deref().unwrap_or("None")
+                ));
+            }
+        }
+    }
+
+    if !changes.is_empty() {
+        println!("---");
+        println!("{ANSI_ITALIC}Changed since the last run:{ANSI_RESET}");
+        changes.iter().for_each(|change| println!("{change}"));
+    }
+}
+
+fn run_tests(puzzle: Puzzle, release: bool) {
+    let mut args = vec!["test".to_string(), "--bin".to_string(), puzzle.bin_name()];
+    if release {
+        args.push("--release".into());
+    }
+
+    if let Err(e) = Command::new("cargo").args(&args).status() {
+        eprintln!("Failed to run the tests of {puzzle}: {e}");
+    }
+}
@@ -0,0 +1,143 @@
+/// Compares benchmark timings against a baseline to catch performance regressions.
+use std::time::Duration;
+
+use crate::template::runner::PARSE_STEP;
+use crate::template::table::print_table;
+use crate::template::timings::Timings;
+use crate::template::Day;
+
+/// The change in time of a single part between the baseline and the current run.
+#[derive(Clone, Debug)]
+pub struct Delta {
+    pub day: Day,
+    pub part: u8,
+    pub baseline: Option<Duration>,
+    pub current: Duration,
+}
+
+impl Delta {
+    /// Relative change in percent, positive if the part got slower.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percent(&self) -> Option<f64> {
+        let baseline = self.baseline?.as_nanos() as f64;
+        if baseline == 0.0 {
+            return None;
+        }
+        Some((self.current.as_nanos() as f64 - baseline) / baseline * 100.0)
+    }
+
+    /// Whether the part got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent().is_some_and(|p| p > threshold)
+    }
+
+    fn status(&self, threshold: f64) -> &'static str {
+        match self.percent() {
+            None => "new",
+            Some(p) if p > threshold => "✖ regressed",
+            Some(p) if p < -threshold => "✔ improved",
+            Some(_) => "unchanged",
+        }
+    }
+}
+
+/// Pairs every timed part and parse step of `current` with the same one in `baseline`.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<Delta> {
+    current
+        .data
+        .iter()
+        .flat_map(|timing| {
+            let stored = baseline.data.iter().find(|t| t.day == timing.day);
+
+            [PARSE_STEP, 1, 2].into_iter().filter_map(move |part| {
+                Some(Delta {
+                    day: timing.day,
+                    part,
+                    baseline: stored.and_then(|t| t.part_time(part)),
+                    current: timing.part_time(part)?,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Print a table of the deltas, marking parts that changed by more than `threshold` percent.
+pub fn print_deltas(deltas: &[Delta], threshold: f64) {
+    let rows: Vec<Vec<String>> = deltas
+        .iter()
+        .map(|d| {
+            vec![
+                d.day.to_string(),
+                if d.part == PARSE_STEP {
+                    "parse".into()
+                } else {
+                    d.part.to_string()
+                },
+                d.baseline
+                    .map_or_else(|| "-".into(), |b| format!("{b:.1?}")),
+                format!("{:.1?}", d.current),
+                d.percent()
+                    .map_or_else(|| "-".into(), |p| format!("{p:+.1}%")),
+                d.status(threshold).into(),
+            ]
+        })
+        .collect();
+
+    print_table(
+        &["Day", "Part", "Baseline", "Current", "Change", "Status"],
+        &rows,
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::compare;
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+        template::Day,
+    };
+
+    fn timing(day: Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            part_1: part_1.map(String::from),
+            part_2: part_2.map(String::from),
+            ..Timing::new(day)
+        }
+    }
+
+    #[test]
+    fn compares_parts() {
+        let baseline = Timings {
+            data: vec![timing(day!(1), Some("10.0ms"), Some("20.0ms"))],
+            history: vec![],
+        };
+        let current = Timings {
+            data: vec![
+                timing(day!(1), Some("12.0ms"), Some("15.0ms")),
+                timing(day!(2), Some("1.0ms"), None),
+            ],
+            history: vec![],
+        };
+
+        let deltas = compare(&baseline, &current);
+        assert_eq!(deltas.len(), 3);
+
+        assert_eq!(deltas[0].day, day!(1));
+        assert_eq!(deltas[0].baseline, Some(Duration::from_millis(10)));
+        assert_eq!(deltas[0].percent().unwrap().round(), 20.0);
+        assert!(deltas[0].is_regression(10.0));
+        assert!(!deltas[0].is_regression(25.0));
+
+        assert_eq!(deltas[1].percent().unwrap().round(), -25.0);
+        assert!(!deltas[1].is_regression(10.0));
+
+        assert_eq!(deltas[2].day, day!(2));
+        assert_eq!(deltas[2].percent(), None);
+        assert!(!deltas[2].is_regression(0.0));
+    }
+}
@@ -2,6 +2,8 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::template::Year;
+
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
@@ -9,6 +11,7 @@ use chrono::{Datelike, FixedOffset, Utc};
 const SERVER_UTC_OFFSET: i32 = -5;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// Some years have fewer days, see [`Year::day_count`].
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -45,11 +48,14 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
-    pub fn today() -> Option<Self> {
+    /// Returns the current day if it's a day of the event of `year` in december, `None` otherwise.
+    pub fn today(year: Year) -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        if today.year() == i32::from(year.into_inner())
+            && today.month() == 12
+            && today.day() <= u32::from(year.day_count())
+        {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
@@ -100,20 +106,23 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// An iterator that yields every day of a year's event, from the 1st to its last day.
+pub fn all_days(year: Year) -> AllDays {
+    AllDays::new(year)
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of a year's event, from the 1st to its last day.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(year: Year) -> Self {
+        Self {
+            current: 1,
+            last: year.day_count(),
+        }
     }
 }
 
@@ -121,10 +130,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and the day count of a year is not above 25.
         let day = Day(self.current);
         self.current += 1;
 
@@ -135,6 +144,7 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+/// This only checks that the day is in range 1 to 25, not that it is part of a given year.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
@@ -155,10 +165,11 @@ macro_rules! day {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{all_days, Day};
+    use crate::year;
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = all_days(year!(2024));
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +198,14 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_iterator_short_event() {
+        let days: Vec<Day> = all_days(year!(2025)).collect();
+
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.last(), Some(&Day(12)));
+    }
 }
 
 /* -------------------------------------------------------------------------- */
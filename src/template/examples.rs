@@ -0,0 +1,450 @@
+/// Example inputs with their expected answers, listed in a manifest per day.
+/// Every `solution!` generates a test that checks its parts against the manifest of its day,
+/// so adding an example only needs a new entry in `data/{year}/examples/{day}.json`:
+///
+/// ```json
+/// {
+///   "examples": [
+///     { "file": "16.txt", "answers": { "1": "11048", "2": "64" } },
+///     { "name": "paths cross", "file": "16-3.txt", "answers": { "2": "22" } }
+///   ]
+/// }
+/// ```
+use std::{collections::HashMap, fmt::Display, fs, io::ErrorKind, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{workspace_path, Puzzle};
+
+/// The expected answer of one part for an example input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// Describes what the example covers, if the file name does not.
+    pub name: Option<String>,
+    /// The input file, relative to the examples folder.
+    pub file: String,
+    pub part: u8,
+    pub answer: String,
+}
+
+impl Display for Example {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} part {}", self.file, self.part)?;
+        if let Some(name) = &self.name {
+            write!(f, " ({name})")?;
+        }
+        Ok(())
+    }
+}
+
+/// The examples of a single day.
+/// Can be serialized from JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Manifest {
+    pub examples: Vec<Example>,
+}
+
+impl Manifest {
+    /// Reads the manifest of a puzzle. If not present, returns an empty manifest.
+    pub fn read_from_file(puzzle: Puzzle) -> Result<Self, String> {
+        let path = workspace_path(puzzle.data_path("examples", "json"));
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Manifest::try_from(contents)
+                .map_err(|e| format!("Invalid manifest \"{}\": {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Could not read \"{}\": {e}", path.display())),
+        }
+    }
+}
+
+impl Manifest {
+    /// Dehydrate the manifest of a puzzle to a JSON file.
+    pub fn store_file(&self, puzzle: Puzzle) -> Result<(), std::io::Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(workspace_path(puzzle.data_path("examples", "json")))?;
+        json.format_to(&mut file)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An example found in the description of a part, as written by `download` or `read`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DescribedExample {
+    pub part: u8,
+    /// The first code block of the part.
+    pub input: Option<String>,
+    /// The last emphasized code of the part, e.g. `` `*11*` ``, which usually is the example's answer.
+    pub answer: Option<String>,
+}
+
+/// Finds the example input and answer of every part in a puzzle description.
+/// The second part is only described once the first one is solved.
+pub fn extract_from_description(markdown: &str) -> Vec<DescribedExample> {
+    let sections = match markdown.split_once("## --- Part Two ---") {
+        Some((one, two)) => vec![(1, one), (2, two)],
+        None => vec![(1, markdown)],
+    };
+
+    sections
+        .into_iter()
+        .map(|(part, section)| {
+            let mut example = DescribedExample {
+                part,
+                ..Default::default()
+            };
+            let mut block: Option<Vec<&str>> = None;
+
+            for line in section.lines() {
+                match (line.trim_end() == "```", block.as_mut()) {
+                    (true, None) => block = Some(vec![]),
+                    (true, Some(lines)) => {
+                        example.input.get_or_insert_with(|| lines.join("\n") + "\n");
+                        block = None;
+                    }
+                    (false, Some(lines)) => lines.push(line),
+                    (false, None) => {
+                        if let Some(answer) = line
+                            .split("`*")
+                            .skip(1)
+                            .filter_map(|s| s.split_once("*`"))
+                            .last()
+                        {
+                            example.answer = Some(answer.0.to_string());
+                        }
+                    }
+                }
+            }
+
+            example
+        })
+        .collect()
+}
+
+/// Fills in the expected answer of a test that `scaffold` created, e.g. `test_part_one`.
+/// With a `file_part`, the test reads that example file (`NN-2.txt`) instead of `NN.txt`.
+/// Returns `None` if the test was changed since it was scaffolded, or the answer is not a number.
+pub fn fill_in_scaffolded_test(
+    source: &str,
+    test: &str,
+    answer: &str,
+    file_part: Option<u8>,
+) -> Option<String> {
+    answer.parse::<u64>().ok()?;
+
+    let start = source.find(&format!("fn {test}()"))?;
+    let end = start + source[start..].find("\n    }\n")?;
+    let body = &source[start..end];
+
+    if !body.contains("assert_eq!(result, None);") {
+        return None;
+    }
+
+    let mut body = body.replace(
+        "assert_eq!(result, None);",
+        &format!("assert_eq!(result, Some({answer}));"),
+    );
+    if let Some(part) = file_part {
+        body = body.replace(
+            "read_file(\"examples\", PUZZLE)",
+            &format!("read_file_part(\"examples\", PUZZLE, {part})"),
+        );
+    }
+
+    Some(format!("{}{body}{}", &source[..start], &source[end..]))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Runs one part of a solution on an example input, returning its answer or the error it failed with.
+pub type ExampleFn<'a> = &'a dyn Fn(&str) -> Result<Option<String>, String>;
+
+/// Runs every example of a puzzle's manifest through the given parts.
+/// Examples of parts that are not given are skipped.
+///
+/// # Panics
+/// If the manifest cannot be read, or any example does not produce its expected answer.
+pub fn check(puzzle: Puzzle, parts: &[(u8, ExampleFn)]) {
+    let manifest = Manifest::read_from_file(puzzle).unwrap_or_else(|e| panic!("{e}"));
+    let examples_dir = workspace_path(puzzle.year.data_dir().join("examples"));
+
+    let failures: Vec<String> = manifest
+        .examples
+        .iter()
+        .filter_map(|example| {
+            let (_, run_part) = parts.iter().find(|(part, _)| *part == example.part)?;
+
+            let Ok(input) = fs::read_to_string(examples_dir.join(&example.file)) else {
+                return Some(format!("{example}: could not read input file"));
+            };
+
+            match run_part(&input) {
+                Ok(Some(answer)) if answer == example.answer => None,
+                Ok(Some(answer)) => Some(format!(
+                    "{example}: expected {}, got {answer}",
+                    example.answer
+                )),
+                Ok(None) => Some(format!("{example}: expected {}, got none", example.answer)),
+                Err(error) => Some(format!(
+                    "{example}: expected {}, failed with {error}",
+                    example.answer
+                )),
+            }
+        })
+        .collect();
+
+    assert!(
+        failures.is_empty(),
+        "{} example(s) failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for Manifest {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let examples = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("examples")
+            .ok_or("expected JSON document to have key `examples`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.examples` to be an array.")?;
+
+        let mut manifest = Manifest::default();
+
+        for entry in examples {
+            let entry = entry
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected `example` to be an object.")?;
+
+            let name = match entry.get("name") {
+                None => None,
+                Some(v) => Some(
+                    v.get::<String>()
+                        .ok_or("expected `example.name` to be a string.")?
+                        .clone(),
+                ),
+            };
+
+            let file = entry
+                .get("file")
+                .and_then(|v| v.get::<String>())
+                .ok_or("expected `example.file` to be a string.")?;
+
+            let answers = entry
+                .get("answers")
+                .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+                .ok_or("expected `example.answers` to be an object.")?;
+
+            let mut parts: Vec<(u8, String)> = answers
+                .iter()
+                .map(|(part, answer)| {
+                    let part = part
+                        .parse()
+                        .ok()
+                        .filter(|part| [1, 2].contains(part))
+                        .ok_or("expected the keys of `example.answers` to be 1 or 2.")?;
+                    let answer = answer
+                        .get::<String>()
+                        .ok_or("expected `example.answers` to hold strings.")?;
+                    Ok((part, answer.clone()))
+                })
+                .collect::<Result<_, String>>()?;
+
+            // JSON objects are unordered, keep the examples in part order.
+            parts.sort();
+
+            manifest
+                .examples
+                .extend(parts.into_iter().map(|(part, answer)| Example {
+                    name: name.clone(),
+                    file: file.clone(),
+                    part,
+                    answer,
+                }));
+        }
+
+        Ok(manifest)
+    }
+}
+
+impl From<&Manifest> for JsonValue {
+    fn from(value: &Manifest) -> Self {
+        // examples of the same file share one entry.
+        let mut entries: Vec<(&Option<String>, &str, HashMap<String, JsonValue>)> = vec![];
+
+        for example in &value.examples {
+            let answer = JsonValue::String(example.answer.clone());
+            match entries
+                .iter_mut()
+                .find(|(name, file, _)| **name == example.name && *file == example.file)
+            {
+                Some((_, _, answers)) => {
+                    answers.insert(example.part.to_string(), answer);
+                }
+                None => entries.push((
+                    &example.name,
+                    &example.file,
+                    HashMap::from([(example.part.to_string(), answer)]),
+                )),
+            }
+        }
+
+        let examples = entries
+            .into_iter()
+            .map(|(name, file, answers)| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                if let Some(name) = name {
+                    map.insert("name".into(), JsonValue::String(name.clone()));
+                }
+                map.insert("file".into(), JsonValue::String(file.into()));
+                map.insert("answers".into(), JsonValue::Object(answers));
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        JsonValue::Object(HashMap::from([(
+            "examples".into(),
+            JsonValue::Array(examples),
+        )]))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{
+        extract_from_description, fill_in_scaffolded_test, DescribedExample, Example, Manifest,
+    };
+
+    #[test]
+    fn parses_manifests() {
+        let manifest = Manifest::try_from(
+            r#"{ "examples": [
+                { "file": "16.txt", "answers": { "2": "64", "1": "11048" } },
+                { "name": "paths cross", "file": "16-3.txt", "answers": { "2": "22" } }
+            ] }"#
+                .to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(manifest.examples.len(), 3);
+        assert_eq!(
+            manifest.examples[0],
+            Example {
+                name: None,
+                file: "16.txt".into(),
+                part: 1,
+                answer: "11048".into(),
+            }
+        );
+        assert_eq!(manifest.examples[1].part, 2);
+        assert_eq!(manifest.examples[2].name.as_deref(), Some("paths cross"));
+        assert_eq!(
+            manifest.examples[2].to_string(),
+            "16-3.txt part 2 (paths cross)"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_manifests() {
+        assert!(Manifest::try_from(r#"{ "examples": {} }"#.to_string()).is_err());
+        assert!(Manifest::try_from(
+            r#"{ "examples": [{ "file": "01.txt", "answers": { "3": "1" } }] }"#.to_string()
+        )
+        .is_err());
+        assert!(Manifest::try_from(
+            r#"{ "examples": [{ "file": "01.txt", "answers": { "1": 1 } }] }"#.to_string()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn roundtrips_manifests() {
+        let manifest = Manifest::try_from(
+            r#"{ "examples": [
+                { "file": "01.txt", "answers": { "1": "11", "2": "31" } },
+                { "name": "small", "file": "01-2.txt", "answers": { "2": "4" } }
+            ] }"#
+                .to_string(),
+        )
+        .unwrap();
+
+        let json = JsonValue::from(&manifest).stringify().unwrap();
+        let parsed = Manifest::try_from(json).unwrap();
+        assert_eq!(parsed.examples, manifest.examples);
+    }
+
+    #[test]
+    fn extracts_examples_from_descriptions() {
+        let markdown = [
+            "## --- Day 1: Test ---",
+            "",
+            "For example:",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "The distance is `*2*`, so the total is `*11*`.",
+            "",
+            "*What is the total distance?*",
+            "",
+            "## --- Part Two ---",
+            "",
+            "The similarity score is `*31*`.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            extract_from_description(&markdown),
+            vec![
+                DescribedExample {
+                    part: 1,
+                    input: Some("3   4\n4   3\n".into()),
+                    answer: Some("11".into()),
+                },
+                DescribedExample {
+                    part: 2,
+                    input: None,
+                    answer: Some("31".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn fills_in_scaffolded_tests() {
+        let source = include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/templates/default.txt"
+        ));
+
+        let filled = fill_in_scaffolded_test(source, "test_part_two", "31", Some(2)).unwrap();
+        assert!(filled.contains("assert_eq!(result, Some(31));"));
+        assert!(filled.contains(r#"read_file_part("examples", PUZZLE, 2)"#));
+        // the other test stays untouched.
+        assert!(filled.contains("assert_eq!(result, None);"));
+
+        assert_eq!(
+            fill_in_scaffolded_test(&filled, "test_part_two", "31", None),
+            None
+        );
+        assert_eq!(
+            fill_in_scaffolded_test(source, "test_part_one", "abc", None),
+            None
+        );
+    }
+}
@@ -0,0 +1,124 @@
+/// Reads the current commit from the local git repository without invoking `git`.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::template::workspace_path;
+
+/// The hash of the commit `HEAD` points to in the repository of the workspace, if any.
+pub fn head_commit() -> Option<String> {
+    read_head(&git_dir(&workspace_path(".git"))?)
+}
+
+/// Resolves `.git` to the git directory. Worktrees and submodules use a `.git` file pointing to it.
+fn git_dir(dot_git: &Path) -> Option<PathBuf> {
+    if dot_git.is_dir() {
+        return Some(dot_git.to_path_buf());
+    }
+
+    let contents = fs::read_to_string(dot_git).ok()?;
+    let target = contents.strip_prefix("gitdir:")?.trim();
+    Some(dot_git.parent()?.join(target))
+}
+
+fn read_head(git_dir: &Path) -> Option<String> {
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    let Some(reference) = head.strip_prefix("ref:").map(str::trim) else {
+        // a detached `HEAD` holds the commit hash itself.
+        return is_hash(head).then(|| head.to_string());
+    };
+
+    // refs of linked worktrees live in the main repository.
+    let common_dir = fs::read_to_string(git_dir.join("commondir"))
+        .map_or_else(|_| git_dir.to_path_buf(), |dir| git_dir.join(dir.trim()));
+
+    [git_dir, common_dir.as_path()].iter().find_map(|dir| {
+        let loose = fs::read_to_string(dir.join(reference)).ok();
+        loose
+            .map(|hash| hash.trim().to_string())
+            .or_else(|| packed_ref(dir, reference))
+            .filter(|hash| is_hash(hash))
+    })
+}
+
+/// Looks up a reference in `packed-refs`, where each line reads `<hash> <reference>`.
+fn packed_ref(git_dir: &Path, reference: &str) -> Option<String> {
+    fs::read_to_string(git_dir.join("packed-refs"))
+        .ok()?
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(_, name)| *name == reference)
+        .map(|(hash, _)| hash.to_string())
+}
+
+fn is_hash(s: &str) -> bool {
+    s.len() >= 40 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use super::read_head;
+
+    const HASH: &str = "0123456789abcdef0123456789abcdef01234567";
+
+    fn mock_git_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-git-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        for (path, contents) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        dir
+    }
+
+    #[test]
+    fn reads_loose_refs() {
+        let dir = mock_git_dir(
+            "loose",
+            &[
+                ("HEAD", "ref: refs/heads/main\n"),
+                ("refs/heads/main", &format!("{HASH}\n")),
+            ],
+        );
+        assert_eq!(read_head(&dir), Some(HASH.into()));
+    }
+
+    #[test]
+    fn reads_packed_refs() {
+        let dir = mock_git_dir(
+            "packed",
+            &[
+                ("HEAD", "ref: refs/heads/main\n"),
+                (
+                    "packed-refs",
+                    &format!(
+                        "# pack-refs with: peeled fully-peeled sorted\n{HASH} refs/heads/main\n"
+                    ),
+                ),
+            ],
+        );
+        assert_eq!(read_head(&dir), Some(HASH.into()));
+    }
+
+    #[test]
+    fn reads_detached_head() {
+        let dir = mock_git_dir("detached", &[("HEAD", HASH)]);
+        assert_eq!(read_head(&dir), Some(HASH.into()));
+    }
+
+    #[test]
+    fn handles_unborn_branches() {
+        let dir = mock_git_dir("unborn", &[("HEAD", "ref: refs/heads/main\n")]);
+        assert_eq!(read_head(&dir), None);
+    }
+}
@@ -0,0 +1,225 @@
+/// Selects the file a solution binary runs on.
+use std::{
+    env, fs,
+    io::{self, IsTerminal, Read},
+    path::PathBuf,
+    process,
+    sync::OnceLock,
+};
+
+use crate::template::{workspace_path, Puzzle, Year};
+
+/// The input a solution binary runs on, selected with `--example [k]`, `--input path` or by piping it in.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input, e.g. `data/2024/inputs/01.txt`.
+    #[default]
+    Puzzle,
+    /// An example of the day, e.g. `data/2024/examples/01.txt`, or `01-2.txt` for `--example 2`.
+    Example(Option<u8>),
+    /// Any other file, relative to the working directory.
+    File(PathBuf),
+    /// Standard input, selected with `--input -` or by piping into the binary.
+    Stdin,
+}
+
+/// The input source [`read_input`] settled on, including piped input that the arguments do not show.
+static SELECTED: OnceLock<InputSource> = OnceLock::new();
+
+/// Reads the input of a solution binary, exiting with a message that names the file if it cannot be read.
+/// Without `--example` or `--input`, input piped into the binary takes precedence over the puzzle input.
+pub fn read_input(puzzle: Puzzle) -> String {
+    let source = InputSource::from_args();
+    let piped = if source.is_puzzle() && !io::stdin().is_terminal() {
+        read_stdin().ok()
+    } else {
+        None
+    };
+
+    let (source, piped) = source.or_piped(piped);
+    let input = match piped {
+        Some(input) => Ok(input),
+        None => source.read(puzzle),
+    };
+
+    SELECTED.get_or_init(|| source);
+
+    input.unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    })
+}
+
+fn read_stdin() -> io::Result<String> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
+
+impl InputSource {
+    /// Reads the `--example` and `--input` arguments of a solution binary.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        if let Some(index) = args.iter().position(|x| x == "--example") {
+            return Self::Example(args.get(index + 1).and_then(|k| k.parse().ok()));
+        }
+
+        let Some(index) = args.iter().position(|x| x == "--input") else {
+            return Self::Puzzle;
+        };
+
+        match args.get(index + 1).map(String::as_str) {
+            Some("-") => Self::Stdin,
+            Some(path) => Self::File(path.into()),
+            None => {
+                eprintln!("Unexpected command-line input. Input: cargo solve 1 --input path");
+                process::exit(1);
+            }
+        }
+    }
+
+    /// The input this process runs on: the one [`read_input`] picked, or the one the arguments select.
+    pub fn selected() -> Self {
+        SELECTED.get().cloned().unwrap_or_else(Self::from_args)
+    }
+
+    /// Takes input piped into the binary over the puzzle input.
+    /// An empty stdin (e.g. `/dev/null`) falls back to the puzzle input.
+    fn or_piped(self, piped: Option<String>) -> (Self, Option<String>) {
+        match piped.filter(|input| !input.is_empty()) {
+            Some(input) if self.is_puzzle() => (Self::Stdin, Some(input)),
+            _ => (self, None),
+        }
+    }
+
+    /// Whether this is the puzzle input, the only one that answers can be checked against and submitted for.
+    pub fn is_puzzle(&self) -> bool {
+        *self == Self::Puzzle
+    }
+
+    /// The arguments passed to a solution binary for this input.
+    pub fn as_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::Example(None) => vec!["--example".into()],
+            Self::Example(Some(k)) => vec!["--example".into(), k.to_string()],
+            Self::File(path) => vec!["--input".into(), path.display().to_string()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// The path of the file, relative to the root of the workspace unless it was given otherwise.
+    /// Standard input has no path.
+    pub fn path(&self, puzzle: Puzzle) -> Option<PathBuf> {
+        match self {
+            Self::Puzzle => Some(puzzle.data_path("inputs", "txt")),
+            Self::Example(None) => Some(puzzle.data_path("examples", "txt")),
+            Self::Example(Some(k)) => Some(
+                puzzle
+                    .year
+                    .data_dir()
+                    .join("examples")
+                    .join(format!("{}-{k}.txt", puzzle.day)),
+            ),
+            Self::File(path) => Some(path.clone()),
+            Self::Stdin => None,
+        }
+    }
+
+    /// Reads the input. The error names the file that is missing and how to get it.
+    pub fn read(&self, puzzle: Puzzle) -> Result<String, String> {
+        let Some(path) = self.path(puzzle) else {
+            return read_stdin().map_err(|e| format!("Could not read the input from stdin: {e}"));
+        };
+
+        let path = match self {
+            Self::File(_) => path,
+            _ => workspace_path(path),
+        };
+
+        fs::read_to_string(&path).map_err(|e| {
+            let mut message = format!("Could not read input file \"{}\": {e}.", path.display());
+            if let Some(hint) = self.hint(puzzle) {
+                message.push(' ');
+                message.push_str(&hint);
+            }
+            message
+        })
+    }
+
+    /// How to create the file of this input if it is missing.
+    fn hint(&self, puzzle: Puzzle) -> Option<String> {
+        let year_arg = if Year::from_env() == Some(puzzle.year) {
+            String::new()
+        } else {
+            format!(" --year {}", puzzle.year)
+        };
+
+        match self {
+            Self::Puzzle => Some(format!(
+                "Run `cargo download {}{year_arg}` to download it.",
+                puzzle.day
+            )),
+            Self::Example(_) => Some(format!(
+                "Run `cargo examples extract {}{year_arg}` to extract the examples from the puzzle description, or add it yourself.",
+                puzzle.day
+            )),
+            Self::File(_) | Self::Stdin => None,
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::InputSource;
+    use crate::{day, template::Puzzle, year};
+
+    #[test]
+    fn resolves_paths() {
+        let puzzle = Puzzle::new(year!(2024), day!(3));
+        assert_eq!(
+            InputSource::Puzzle.path(puzzle),
+            Some(PathBuf::from("data/2024/inputs/03.txt"))
+        );
+        assert_eq!(
+            InputSource::Example(None).path(puzzle),
+            Some(PathBuf::from("data/2024/examples/03.txt"))
+        );
+        assert_eq!(
+            InputSource::Example(Some(2)).path(puzzle),
+            Some(PathBuf::from("data/2024/examples/03-2.txt"))
+        );
+        assert_eq!(InputSource::Stdin.path(puzzle), None);
+    }
+
+    #[test]
+    fn passes_arguments() {
+        assert!(InputSource::Puzzle.as_args().is_empty());
+        assert_eq!(InputSource::Example(Some(2)).as_args(), ["--example", "2"]);
+        assert_eq!(
+            InputSource::File("custom.txt".into()).as_args(),
+            ["--input", "custom.txt"]
+        );
+        assert_eq!(InputSource::Stdin.as_args(), ["--input", "-"]);
+    }
+
+    #[test]
+    fn prefers_piped_input() {
+        let piped = || Some("1 2 3".to_string());
+        assert_eq!(
+            InputSource::Puzzle.or_piped(piped()),
+            (InputSource::Stdin, piped())
+        );
+        assert_eq!(
+            InputSource::Puzzle.or_piped(Some(String::new())),
+            (InputSource::Puzzle, None)
+        );
+        assert_eq!(
+            InputSource::Example(None).or_piped(piped()),
+            (InputSource::Example(None), None)
+        );
+    }
+}
@@ -1,68 +1,190 @@
-use std::{env, fs};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
+pub mod input;
+pub mod output;
+pub mod registry;
 pub mod runner;
+pub mod stats;
 
 pub use day::*;
+pub use puzzle::*;
+pub use year::*;
 
+mod answers;
+mod compare_timings;
 mod day;
+mod git;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
+mod table;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
+pub const ANSI_RED: &str = "\x1b[31m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Resolves a path relative to the root of the workspace, so solutions can run from any working directory.
+pub fn workspace_path(path: impl AsRef<Path>) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(path)
+}
+
+/// Helper function that reads a text file of a puzzle to a string, e.g. `data/2024/inputs/01.txt`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, puzzle: Puzzle) -> String {
+    read_to_string(&workspace_path(puzzle.data_path(folder, "txt")))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file_part(folder: &str, puzzle: Puzzle, part: u8) -> String {
+    let filepath = workspace_path(
+        puzzle
+            .year
+            .data_dir()
+            .join(folder)
+            .join(format!("{}-{part}.txt", puzzle.day)),
+    );
+    read_to_string(&filepath)
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+fn read_to_string(filepath: &Path) -> String {
+    fs::read_to_string(filepath)
+        .unwrap_or_else(|e| panic!("could not open input file \"{}\": {e}", filepath.display()))
+}
+
+/// Creates the constants `DAY`, `YEAR` and `PUZZLE` and sets up the input and runner for each part.
+///
+/// The year defaults to the `AOC_YEAR` the solution is compiled with. Solutions of other years pass it with `year = 2023`.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The optional, last parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// With `parse = parse_input`, the input is parsed once and timed on its own,
+/// and `part_one` and `part_two` take a reference to the parsed input instead of the raw `&str`.
+///
+/// The binary runs on the puzzle input, unless it is given `--example [k]` or `--input path`, see [`input::read_input`].
+///
+/// Every solution gets a `test_examples` test, see [`examples`] for how to list example inputs and their answers.
+/// The test is ignored for days without a manifest of examples.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, year = $year:expr)?) => {
+        $crate::solution!(@impl $day, ($($year)?), [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr $(, year = $year:expr)?, 1) => {
+        $crate::solution!(@impl $day, ($($year)?), [part_one, 1]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:expr $(, year = $year:expr)?, 2) => {
+        $crate::solution!(@impl $day, ($($year)?), [part_two, 2]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr $(, year = $year:expr)?, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, ($($year)?), $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr $(, year = $year:expr)?, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl_parsed $day, ($($year)?), $parse, [part_one, 1]);
+    };
+    ($day:expr $(, year = $year:expr)?, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl_parsed $day, ($($year)?), $parse, [part_two, 2]);
+    };
+
+    (@year) => {
+        $crate::template::Year::__from_compile_env(option_env!("AOC_YEAR"))
+    };
+    (@year $year:expr) => {
+        $crate::year!($year)
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@puzzle $day:expr, ($($year:expr)?)) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The year of the current day.
+        const YEAR: $crate::template::Year = $crate::solution!(@year $($year)?);
+
+        /// The puzzle solved by this binary.
+        const PUZZLE: $crate::template::Puzzle = $crate::template::Puzzle::new(YEAR, DAY);
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
+    };
+
+    // `test_examples` calls `check_examples`, and is ignored if the day has no manifest. See `build.rs`.
+    (@example_test) => {
+        #[cfg(test)]
+        include!(concat!(env!("OUT_DIR"), "/examples/", env!("CARGO_BIN_NAME"), ".rs"));
+    };
+
+    (@impl $day:expr, $year:tt, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@puzzle $day, $year);
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = $crate::template::input::read_input(PUZZLE);
+            let results = [$( run_part($func, &input, PUZZLE, $part), )*];
+            $crate::template::output::print_document(&results, RunOptions::from_args().format);
+        }
+
+        /// Checks the parts against the examples in the manifest of this day.
+        #[cfg(test)]
+        fn check_examples() {
+            $crate::template::examples::check(PUZZLE, &[$(
+                ($part, &|input: &str| $crate::template::runner::PartOutput::into_answer($func(input))),
+            )*]);
         }
+
+        $crate::solution!(@example_test);
+
+        /// The parts of this day, for running them from the main binary.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution = $crate::template::registry::Solution {
+            puzzle: PUZZLE,
+            parts: &[$(
+                ($part, |input, options| $crate::template::runner::run_part_with($func, input, PUZZLE, $part, options)),
+            )*],
+        };
+    };
+
+    (@impl_parsed $day:expr, $year:tt, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@puzzle $day, $year);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = $crate::template::input::read_input(PUZZLE);
+            let (parsed, parse_result) = run_parse($parse, &input, PUZZLE, RunOptions::from_args());
+            let results = [parse_result, $( run_part($func, &parsed, PUZZLE, $part), )*];
+            $crate::template::output::print_document(&results, RunOptions::from_args().format);
+        }
+
+        /// Checks the parts against the examples in the manifest of this day.
+        #[cfg(test)]
+        fn check_examples() {
+            $crate::template::examples::check(PUZZLE, &[$(
+                ($part, &|input: &str| $crate::template::runner::PartOutput::into_answer($func(&$parse(input)))),
+            )*]);
+        }
+
+        $crate::solution!(@example_test);
+
+        /// The parse step and parts of this day, for running them from the main binary.
+        /// Each part parses the input again, outside of its timing.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution = $crate::template::registry::Solution {
+            puzzle: PUZZLE,
+            parts: &[
+                ($crate::template::runner::PARSE_STEP, |input, options| $crate::template::runner::run_parse($parse, input, PUZZLE, options).1),
+                $(
+                    ($part, |input, options| $crate::template::runner::run_part_with($func, &$parse(input), PUZZLE, $part, options)),
+                )*
+            ],
+        };
     };
 }
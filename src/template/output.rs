@@ -0,0 +1,332 @@
+/// Machine-readable output of part results.
+/// Solution binaries always report to the main binary as JSON Lines, independent of the format shown to the user.
+use std::{
+    collections::HashMap, env, error::Error, fmt::Display, process, str::FromStr, time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::runner::{PartResult, PARSE_STEP};
+use crate::template::stats::BenchStats;
+use crate::template::{Day, Puzzle, Year};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// A single JSON array of all part records, printed once every part has run.
+    Json,
+    /// One JSON object per part record, printed as soon as the part has run.
+    JsonLines,
+}
+
+impl OutputFormat {
+    /// Reads the `--format` argument of a solution binary.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let Some(index) = args.iter().position(|x| x == "--format") else {
+            return Self::default();
+        };
+
+        match args.get(index + 1).map(|s| s.parse()) {
+            Some(Ok(format)) => format,
+            _ => {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --format json");
+                process::exit(1);
+            }
+        }
+    }
+
+    pub fn is_text(self) -> bool {
+        self == Self::Text
+    }
+
+    /// The argument passed to a solution binary for this format.
+    pub fn as_arg(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Json => "json",
+            Self::JsonLines => "jsonl",
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "jsonl" => Ok(Self::JsonLines),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `text`, `json` or `jsonl`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Serializes a part result to a single line of JSON.
+pub fn to_json_line(result: &PartResult) -> String {
+    JsonValue::from(result).stringify().unwrap()
+}
+
+/// Parses a line of JSON output as a part result.
+pub fn parse_json_line(line: &str) -> Result<PartResult, String> {
+    let json = JsonValue::from_str(line).or(Err("not valid JSON."))?;
+    PartResult::try_from(&json)
+}
+
+/// Prints the part results as a single JSON array if that is the requested format.
+pub fn print_document(results: &[PartResult], format: OutputFormat) {
+    if format == OutputFormat::Json {
+        let json = JsonValue::Array(results.iter().map(JsonValue::from).collect());
+        println!("{}", json.stringify().unwrap());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.puzzle.year.into_inner())),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day.to_string()),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = value.duration.as_nanos() as f64;
+        map.insert("duration_nanos".into(), JsonValue::Number(nanos));
+
+        #[allow(clippy::cast_precision_loss)]
+        let samples = value.samples as f64;
+        map.insert("samples".into(), JsonValue::Number(samples));
+
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        let status = if value.part == PARSE_STEP {
+            "parsed"
+        } else if value.error.is_some() {
+            "failed"
+        } else if value.answer.is_some() {
+            "solved"
+        } else {
+            "unsolved"
+        };
+        map.insert("status".into(), JsonValue::String(status.into()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<f64>())
+            .and_then(|year| Year::new(*year as u16))
+            .ok_or("Expected record.year to be a Year struct.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| [0.0, 1.0, 2.0].contains(*part))
+            .map(|part| *part as u8)
+            .ok_or("Expected record.part to be 0, 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let error = match json.get("error") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .ok_or("Expected record.error to be null or string.")?,
+            ),
+            _ => None,
+        };
+
+        let duration_nanos = json
+            .get("duration_nanos")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected record.duration_nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected record.samples to be a number.")?;
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        Ok(PartResult {
+            puzzle: Puzzle::new(year, day),
+            part,
+            answer: answer.cloned(),
+            error: error.cloned(),
+            duration: Duration::from_nanos(*duration_nanos as u64),
+            samples: *samples as u128,
+            stats,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_json_line, to_json_line, OutputFormat};
+    use crate::{
+        day,
+        template::{runner::PartResult, stats::BenchStats, Puzzle},
+        year,
+    };
+
+    #[test]
+    fn roundtrips_records() {
+        let result = PartResult {
+            puzzle: Puzzle::new(year!(2024), day!(7)),
+            part: 2,
+            answer: Some("1234".into()),
+            error: None,
+            duration: Duration::from_nanos(74_130),
+            samples: 10_000,
+            stats: BenchStats::from_samples(&[
+                Duration::from_nanos(74_000),
+                Duration::from_nanos(74_260),
+            ]),
+        };
+
+        let line = to_json_line(&result);
+        assert!(!line.contains('\n'));
+        assert!(line.contains(r#""status":"solved""#));
+
+        let parsed = parse_json_line(&line).unwrap();
+        assert_eq!(parsed.puzzle, Puzzle::new(year!(2024), day!(7)));
+        assert_eq!(parsed.part, 2);
+        assert_eq!(parsed.answer, Some("1234".into()));
+        assert_eq!(parsed.duration, Duration::from_nanos(74_130));
+        assert_eq!(parsed.samples, 10_000);
+        assert_eq!(parsed.stats, result.stats);
+    }
+
+    #[test]
+    fn parses_unsolved_records() {
+        let parsed = parse_json_line(
+            r#"{"year":2024,"day":"01","part":1,"answer":null,"duration_nanos":12,"samples":1,"status":"unsolved"}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.answer, None);
+        assert_eq!(parsed.stats, None);
+    }
+
+    #[test]
+    fn parses_parse_step_records() {
+        let result = PartResult {
+            puzzle: Puzzle::new(year!(2024), day!(5)),
+            part: 0,
+            answer: None,
+            error: None,
+            duration: Duration::from_nanos(1_200),
+            samples: 1,
+            stats: None,
+        };
+
+        let line = to_json_line(&result);
+        assert!(line.contains(r#""status":"parsed""#));
+        assert_eq!(parse_json_line(&line).unwrap().part, 0);
+    }
+
+    #[test]
+    fn roundtrips_failed_records() {
+        let result = PartResult {
+            puzzle: Puzzle::new(year!(2024), day!(7)),
+            part: 1,
+            answer: None,
+            error: Some("invalid equation\ncaused by: line 2, column 8: unexpected `x5`".into()),
+            duration: Duration::from_nanos(900),
+            samples: 1,
+            stats: None,
+        };
+
+        let line = to_json_line(&result);
+        assert!(!line.contains('\n'));
+        assert!(line.contains(r#""status":"failed""#));
+        assert_eq!(parse_json_line(&line).unwrap().error, result.error);
+    }
+
+    #[test]
+    fn rejects_other_lines() {
+        assert!(parse_json_line("Part 1: 42 (1.2ms)").is_err());
+        assert!(parse_json_line(r#"{"day":"01"}"#).is_err());
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!(
+            "jsonl".parse::<OutputFormat>().unwrap(),
+            OutputFormat::JsonLines
+        );
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use crate::template::{Day, Year};
+
+/// Identifies a puzzle by its year and day, so several years of advent can live in one workspace.
+///
+/// # Display
+/// This value displays as `YYYY-DD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl Puzzle {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// The name of the solution binary.
+    /// Days of the year configured with `AOC_YEAR` are named `DD`, days of any other year `YYYY-DD`.
+    pub fn bin_name(self) -> String {
+        if Year::from_env() == Some(self.year) {
+            self.day.to_string()
+        } else {
+            self.to_string()
+        }
+    }
+
+    /// The path of the solution binary's source file.
+    pub fn bin_path(self) -> PathBuf {
+        PathBuf::from("src")
+            .join("bin")
+            .join(format!("{}.rs", self.bin_name()))
+    }
+
+    /// The path of a data file of this puzzle, e.g. `data/2024/inputs/01.txt`.
+    pub fn data_path(self, folder: &str, extension: &str) -> PathBuf {
+        self.year
+            .data_dir()
+            .join(folder)
+            .join(format!("{}.{extension}", self.day))
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
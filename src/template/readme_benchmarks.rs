@@ -2,6 +2,7 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::stats::BenchStats;
 use crate::template::timings::Timings;
 use crate::template::Day;
 
@@ -59,18 +60,19 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---:  |".into(),
     ];
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            format_cell(timing.parse, timing.parse_stats),
+            format_part_cell(timing.part_1, timing.part_1_stats, timing.part_1_error),
+            format_part_cell(timing.part_2, timing.part_2_stats, timing.part_2_error)
         ));
     }
 
@@ -81,6 +83,27 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
+/// Shows the median ± standard deviation for benchmarked parts.
+fn format_cell(duration: Option<String>, stats: Option<BenchStats>) -> String {
+    match (duration, stats) {
+        (Some(_), Some(stats)) => format!("{:.1?} ± {:.1?}", stats.median, stats.std_dev),
+        (Some(duration), None) => duration,
+        (None, _) => "-".into(),
+    }
+}
+
+/// Marks parts that failed in the run the timings are from.
+fn format_part_cell(
+    duration: Option<String>,
+    stats: Option<BenchStats>,
+    error: Option<String>,
+) -> String {
+    match error {
+        Some(_) => "failed".into(),
+        None => format_cell(duration, stats),
+    }
+}
+
 fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
     let positions = locate_table(s)?;
     let table = construct_table("##", timings, total_millis);
@@ -99,31 +122,37 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 
 #[cfg(feature = "test_lib")]
 mod tests {
+    use std::time::Duration;
+
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day, template::stats::BenchStats, template::timings::Timing, template::timings::Timings,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    parse: Some("5ms".into()),
                     total_nanos: 9e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
+            history: vec![],
         }
     }
 
@@ -167,11 +196,11 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `5ms` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +209,33 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmark_stats() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2_stats = Some(BenchStats {
+            mean: Duration::from_millis(51),
+            median: Duration::from_millis(50),
+            min: Duration::from_millis(48),
+            max: Duration::from_millis(60),
+            std_dev: Duration::from_micros(2500),
+            ci_95: Duration::from_micros(300),
+            outliers: 1,
+        });
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| [Day 4](./src/bin/04.rs) | `5ms` | `40ms` | `50.0ms ± 2.5ms` |"));
+    }
+
+    #[test]
+    fn format_failed_parts() {
+        let mut timings = get_mock_timings();
+        timings.data[1].part_2 = None;
+        timings.data[1].part_2_error = Some("unexpected `x`".into());
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `failed` |"));
+    }
 }
@@ -0,0 +1,19 @@
+/// Lets the main binary run solutions in-process instead of spawning one cargo process per day.
+/// Every `solution!` exports a [`Solution`], and `build.rs` generates the list of day modules that collects them.
+use crate::template::runner::{PartResult, RunOptions};
+use crate::template::Puzzle;
+
+/// Runs one part of a solution on the given input. The result type of the part is erased so days can be stored together.
+pub type PartFn = fn(&str, RunOptions) -> PartResult;
+
+/// The parts of a single puzzle's solution.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: Puzzle,
+    pub parts: &'static [(u8, PartFn)],
+}
+
+/// Finds the solution for a puzzle in a list of registered solutions.
+pub fn find(solutions: &[Solution], puzzle: Puzzle) -> Option<&Solution> {
+    solutions.iter().find(|s| s.puzzle == puzzle)
+}
@@ -1,51 +1,163 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, fmt::Display, fs, io, panic, path::PathBuf};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::output::{self, OutputFormat};
+use crate::template::registry::{self, Solution};
+use crate::template::runner::{emit_part, PartResult, RunOptions};
+use crate::template::{
+    workspace_path, Day, Puzzle, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RED, ANSI_RESET,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Run the solutions for a set of days of `year` in day order.
+/// Days in `solutions` run in this process, all other days run their solution bin.
+/// With `jobs > 1`, every day runs its solution bin instead and up to `jobs` of them run concurrently.
+/// Their output is printed once a day and all days before it are done.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+    solutions: &[Solution],
+    format: OutputFormat,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut results: Vec<PartResult> = vec![];
 
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let puzzles: Vec<Puzzle> = all_days(year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+        .map(|day| Puzzle::new(year, day))
+        .collect();
+
+    // collects the results of a day, printing them first if they came from a solution bin.
+    let mut finish_day = |day: Day, day_results: Vec<PartResult>, is_printed: bool| {
+        if !is_printed {
+            day_results.iter().for_each(|r| emit_part(r, format));
+        }
+
+        if day_results.is_empty() {
+            if format.is_text() {
+                println!("Not solved.");
             }
-            need_space = true;
+        } else {
+            timings.push(child_commands::parse_exec_time(&day_results, day));
+        }
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        results.extend(day_results);
+    };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    if jobs > 1 {
+        child_commands::run_solutions_parallel(
+            &puzzles,
+            is_timed,
+            is_release,
+            jobs,
+            |puzzle, output| {
+                let output = output.unwrap();
+                if format.is_text() {
+                    print_header(puzzle.day, &mut need_space);
+                }
+                output.replay(format);
+                finish_day(puzzle.day, output.records().cloned().collect(), true);
+            },
+        );
+    } else {
+        puzzles.iter().for_each(|puzzle| {
+            if format.is_text() {
+                print_header(puzzle.day, &mut need_space);
+            }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            match registry::find(solutions, *puzzle) {
+                Some(solution) => {
+                    let options = RunOptions { is_timed, format };
+                    match run_in_process(solution, options) {
+                        Ok(day_results) => finish_day(puzzle.day, day_results, true),
+                        Err(e) if format.is_text() => println!("{ANSI_RED}✖ {e}{ANSI_RESET}"),
+                        Err(e) => eprintln!("Day {}: {e}", puzzle.day),
+                    }
+                }
+                None => {
+                    let day_results =
+                        child_commands::run_solution(*puzzle, is_timed, is_release).unwrap();
+                    finish_day(puzzle.day, day_results, false);
+                }
             }
         });
+    }
+
+    output::print_document(&results, format);
 
     if is_timed {
-        let timings = Timings { data: timings };
+        let timings = Timings {
+            data: timings,
+            history: vec![],
+        };
         let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
+        if format.is_text() {
+            println!(
+                "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
         Some(timings)
     } else {
         None
     }
 }
 
+/// Why a registered solution did not run in this process.
+#[derive(Debug)]
+pub enum InProcessError {
+    MissingInput(PathBuf),
+    Panicked,
+}
+
+impl Display for InProcessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InProcessError::MissingInput(path) => {
+                write!(f, "missing input \"{}\"", path.display())
+            }
+            InProcessError::Panicked => f.write_str("failed, the solution panicked"),
+        }
+    }
+}
+
+/// Run a registered solution in this process, printing results as they come in.
+pub fn run_in_process(
+    solution: &Solution,
+    options: RunOptions,
+) -> Result<Vec<PartResult>, InProcessError> {
+    let input_path = workspace_path(solution.puzzle.data_path("inputs", "txt"));
+
+    let Ok(input) = fs::read_to_string(&input_path) else {
+        return Err(InProcessError::MissingInput(input_path));
+    };
+
+    solution
+        .parts
+        .iter()
+        .map(|(_, run_part)| panic::catch_unwind(|| run_part(&input, options)))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| InProcessError::Panicked)
+}
+
+fn print_header(day: Day, need_space: &mut bool) {
+    if *need_space {
+        println!();
+    }
+    *need_space = true;
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -59,48 +171,117 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => f.write_str("could not read the output of the solution"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
 }
 
-/// All solutions live in isolated binaries.
+/// Solutions that are not run in-process live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::input::InputSource;
+    use crate::template::output::{parse_json_line, OutputFormat};
+    use crate::template::runner::{emit_part, PartResult, PARSE_STEP};
+    use crate::template::{workspace_path, Day, Puzzle};
     use std::{
+        collections::HashMap,
         io::{BufRead, BufReader},
-        path::Path,
         process::{Command, Stdio},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc,
+        },
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// Output of a solution bin that was captured instead of forwarded, in the order it was printed.
+    pub struct CapturedOutput {
+        pub lines: Vec<CapturedLine>,
+    }
+
+    pub enum CapturedLine {
+        Record(PartResult),
+        /// A line on stderr, or a line on stdout that is not a record.
+        Stderr(String),
+    }
+
+    impl CapturedOutput {
+        pub fn records(&self) -> impl Iterator<Item = &PartResult> {
+            self.lines.iter().filter_map(|line| match line {
+                CapturedLine::Record(record) => Some(record),
+                CapturedLine::Stderr(_) => None,
+            })
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        /// Prints the records in `format` and everything else to stderr, keeping the order they were printed in.
+        pub fn replay(&self, format: OutputFormat) {
+            for line in &self.lines {
+                match line {
+                    CapturedLine::Record(record) => emit_part(record, format),
+                    CapturedLine::Stderr(line) => eprintln!("{line}"),
+                }
+            }
+        }
+    }
+
+    fn solution_args(
+        puzzle: Puzzle,
+        is_timed: bool,
+        is_release: bool,
+        input: &InputSource,
+    ) -> Vec<String> {
+        let mut args = vec![
+            "run".to_string(),
+            "--quiet".to_string(),
+            "--bin".to_string(),
+            puzzle.bin_name(),
+        ];
 
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
         }
 
+        // have the child report its results as records.
+        args.push("--".into());
+        args.push("--format".into());
+        args.push("jsonl".into());
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push("--time".into());
         }
 
+        args.extend(input.as_args());
+
+        args
+    }
+
+    /// Run the solution bin for a given puzzle, returning its part records.
+    /// Any other output is forwarded to stderr.
+    pub fn run_solution(
+        puzzle: Puzzle,
+        is_timed: bool,
+        is_release: bool,
+    ) -> Result<Vec<PartResult>, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !workspace_path(puzzle.bin_path()).exists() {
+            return Ok(vec![]);
+        }
+
+        let args = solution_args(puzzle, is_timed, is_release, &InputSource::Puzzle);
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr while grabbing records from stdout.
 
+        // the child would take an inherited stdin as its input.
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -108,7 +289,7 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut records = vec![];
 
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
@@ -118,140 +299,238 @@ pub mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            match parse_json_line(&line) {
+                Ok(record) => records.push(record),
+                Err(_) => eprintln!("{line}"),
+            }
         }
 
         thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        Ok(records)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    /// Run the solution bin for a given puzzle on an input, capturing its output without printing it.
+    pub fn capture_solution(
+        puzzle: Puzzle,
+        is_timed: bool,
+        is_release: bool,
+        input: &InputSource,
+    ) -> Result<CapturedOutput, Error> {
+        if !workspace_path(puzzle.bin_path()).exists() {
+            return Ok(CapturedOutput { lines: vec![] });
+        }
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        let mut cmd = Command::new("cargo")
+            .args(solution_args(puzzle, is_timed, is_release, input))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
+        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        // both streams are read at once, so their lines arrive in the order the solution printed them.
+        let (tx, rx) = mpsc::channel();
+        let stderr_tx = tx.clone();
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        let thread = thread::spawn(move || {
+            for line in stderr.lines().map_while(Result::ok) {
+                let _ = stderr_tx.send(CapturedLine::Stderr(line));
+            }
+        });
 
-                timings.total_nanos += nanos;
+        for line in stdout.lines().map_while(Result::ok) {
+            let _ = tx.send(match parse_json_line(&line) {
+                Ok(record) => CapturedLine::Record(record),
+                Err(_) => CapturedLine::Stderr(line),
             });
+        }
 
-        timings
-    }
+        drop(tx);
+        thread.join().unwrap();
+        cmd.wait()?;
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+        Ok(CapturedOutput {
+            lines: rx.into_iter().collect(),
+        })
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    /// Run the solution bins for several puzzles on up to `jobs` threads.
+    /// `on_output` receives the captured output in the order of `puzzles`, as soon as a puzzle and all puzzles before it have finished.
+    pub fn run_solutions_parallel(
+        puzzles: &[Puzzle],
+        is_timed: bool,
+        is_release: bool,
+        jobs: usize,
+        mut on_output: impl FnMut(Puzzle, Result<CapturedOutput, Error>),
+    ) {
+        let next_puzzle = AtomicUsize::new(0);
+        let (tx, rx) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs.min(puzzles.len()) {
+                let tx = tx.clone();
+                let next_puzzle = &next_puzzle;
+
+                scope.spawn(move || {
+                    while let Some(puzzle) =
+                        puzzles.get(next_puzzle.fetch_add(1, Ordering::Relaxed))
+                    {
+                        let output =
+                            capture_solution(*puzzle, is_timed, is_release, &InputSource::Puzzle);
+                        if tx.send((*puzzle, output)).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+
+            // only the worker threads hold senders now, so `rx` ends once they are done.
+            drop(tx);
+
+            let mut finished = HashMap::new();
+            let mut puzzles_to_print = puzzles.iter().peekable();
+
+            for (puzzle, output) in rx {
+                finished.insert(puzzle, output);
+
+                while let Some(output) = puzzles_to_print.peek().and_then(|p| finished.remove(*p)) {
+                    on_output(*puzzles_to_print.next().unwrap(), output);
+                }
+            }
+        });
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    /// Sum up the part records of a day into its timing. Parts without an answer are not timed,
+    /// the errors of failed parts are kept apart from the timings.
+    /// Benchmarked parts are timed by their median. The parse step counts towards the total.
+    pub fn parse_exec_time(records: &[PartResult], day: Day) -> super::Timing {
+        let mut timings = super::Timing::new(day);
+
+        for record in records {
+            match (record.part, &record.error) {
+                (1, Some(error)) => timings.part_1_error = Some(error.clone()),
+                (2, Some(error)) => timings.part_2_error = Some(error.clone()),
+                _ => {}
+            }
+        }
+
+        for record in records
+            .iter()
+            .filter(|r| r.answer.is_some() || r.part == PARSE_STEP)
+        {
+            let time = record.stats.map_or(record.duration, |stats| stats.median);
+            let duration = Some(format!("{time:.1?}"));
+
+            match record.part {
+                PARSE_STEP => (timings.parse, timings.parse_stats) = (duration, record.stats),
+                1 => (timings.part_1, timings.part_1_stats) = (duration, record.stats),
+                2 => (timings.part_2, timings.part_2_stats) = (duration, record.stats),
+                _ => continue,
+            }
+
+            #[allow(clippy::cast_precision_loss)]
+            let nanos = time.as_nanos() as f64;
+            timings.total_nanos += nanos;
+        }
+
+        timings
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
+        use std::time::Duration;
+
         use super::parse_exec_time;
 
-        use crate::day;
+        use crate::{
+            day,
+            template::{runner::PartResult, stats::BenchStats, Puzzle},
+            year,
+        };
+
+        fn record(part: u8, answer: Option<&str>, nanos: u64) -> PartResult {
+            PartResult {
+                puzzle: Puzzle::new(year!(2024), day!(1)),
+                part,
+                answer: answer.map(String::from),
+                error: None,
+                duration: Duration::from_nanos(nanos),
+                samples: 100,
+                stats: None,
+            }
+        }
 
         #[test]
         fn parses_execution_times() {
             let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
+                &[record(1, Some("0"), 74), record(2, Some("10"), 74_130_000)],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
         }
 
         #[test]
         fn parses_with_patterns_in_input() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    record(1, Some("@ @ @ ( ) ms"), 2_000_000_000),
+                    record(2, Some("10s"), 100_000_000),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total_nanos, 2100000000_f64);
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
+        }
+
+        #[test]
+        fn parses_benchmark_stats() {
+            let mut benched = record(1, Some("0"), 120);
+            benched.stats = BenchStats::from_samples(&[
+                Duration::from_nanos(100),
+                Duration::from_nanos(100),
+                Duration::from_nanos(160),
+            ]);
+
+            let res = parse_exec_time(&[benched, record(2, Some("1"), 50)], day!(1));
+            assert_eq!(res.total_nanos, 150_f64);
+            assert_eq!(res.part_1.unwrap(), "100.0ns");
+            assert_eq!(res.part_1_stats.unwrap().max, Duration::from_nanos(160));
+            assert_eq!(res.part_2_stats.is_none(), true);
+        }
+
+        #[test]
+        fn parses_parse_step() {
+            let res = parse_exec_time(&[record(0, None, 500), record(1, Some("0"), 100)], day!(1));
+            assert_eq!(res.total_nanos, 600_f64);
+            assert_eq!(res.parse.unwrap(), "500.0ns");
+            assert_eq!(res.part_1.unwrap(), "100.0ns");
         }
 
         #[test]
         fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            let res = parse_exec_time(&[record(1, None, 10), record(2, None, 20)], day!(1));
+            assert_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_failed_parts() {
+            let mut failed = record(2, None, 20);
+            failed.error = Some("unexpected `x`".into());
+
+            let res = parse_exec_time(&[record(1, Some("0"), 10), failed], day!(1));
+            assert_eq!(res.total_nanos, 10_f64);
+            assert_eq!(res.part_1_error, None);
+            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.part_2_error.unwrap(), "unexpected `x`");
+        }
     }
 }
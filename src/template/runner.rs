@@ -1,35 +1,281 @@
 /// Encapsulates code that interacts with solution functions.
+use std::error::Error;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answers::{is_wrong, Answers};
+use crate::template::input::InputSource;
+use crate::template::output::{self, OutputFormat};
+use crate::template::stats::BenchStats;
+use crate::template::{aoc_client, Day, Puzzle, ANSI_BOLD, ANSI_ITALIC, ANSI_RED, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// How a part is run. Solution binaries read these from their command-line arguments.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    pub is_timed: bool,
+    pub format: OutputFormat,
+}
+
+impl RunOptions {
+    pub fn from_args() -> Self {
+        Self {
+            is_timed: env::args().any(|x| x == "--time"),
+            format: OutputFormat::from_args(),
+        }
+    }
+}
+
+/// The `part` of the result that times the parse step of a solution.
+pub const PARSE_STEP: u8 = 0;
+
+/// The outcome of running a single part.
+#[derive(Clone, Debug)]
+pub struct PartResult {
+    pub puzzle: Puzzle,
+    /// `1` or `2`, or [`PARSE_STEP`] for the parse step, which has no answer.
+    pub part: u8,
+    pub answer: Option<String>,
+    /// The error of a part that failed, followed by one `caused by:` line per cause.
+    pub error: Option<String>,
+    pub duration: Duration,
+    pub samples: u128,
+    /// Only set if the part was benchmarked.
+    pub stats: Option<BenchStats>,
+}
+
+impl PartResult {
+    /// The answer, `None` if the part is not implemented, or the error it failed with.
+    pub fn outcome(&self) -> Result<Option<&str>, &str> {
+        match &self.error {
+            Some(error) => Err(error),
+            None => Ok(self.answer.as_deref()),
+        }
+    }
+}
+
+/// The return type of a part: `Option<T>`, where `None` means the part is not implemented yet,
+/// or `Result<T, E>` for parts that can fail, e.g. on malformed input.
+/// Errors are anything that converts into a `Box<dyn Error>`, like error types or a `String`.
+pub trait PartOutput {
+    /// The answer, `None` if the part is not implemented, or the error with its causes.
+    fn into_answer(self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn into_answer(self) -> Result<Option<String>, String> {
+        Ok(self.map(|answer| answer.to_string()))
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> PartOutput for Result<T, E> {
+    fn into_answer(self) -> Result<Option<String>, String> {
+        match self {
+            Ok(answer) => Ok(Some(answer.to_string())),
+            Err(error) => Err(format_error(&*error.into())),
+        }
+    }
+}
+
+/// Formats an error with one `caused by:` line for every error in its source chain.
+fn format_error(error: &dyn Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+
+    while let Some(cause) = source {
+        message.push_str(&format!("\ncaused by: {cause}"));
+        source = cause.source();
+    }
+
+    message
+}
+
+pub fn run_part<I: Clone, T: PartOutput>(
+    func: impl Fn(I) -> T,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+) -> PartResult {
+    let options = RunOptions::from_args();
+    let result = run_part_with(func, input, puzzle, part, options);
+
+    let Some(answer) = &result.answer else {
+        return result;
+    };
+
+    let mut answers = Answers::read_from_file(puzzle.year);
+
+    match submit_result(answer, puzzle, part, &answers, options.format) {
+        Some(Ok(submission)) => {
+            print_status(&submission.message, options.format);
+            answers.record(puzzle.day, part, answer, submission.verdict);
+            if let Err(e) = answers.store_file(puzzle.year) {
+                eprintln!("Failed to store submitted answer: {e}");
+            }
+        }
+        Some(Err(e)) => eprintln!("Failed to submit result: {e}"),
+        None => {}
+    }
+
+    result
+}
+
+/// Run and print a solution part, returning its result instead of acting on command-line arguments.
+pub fn run_part_with<I: Clone, T: PartOutput>(
+    func: impl Fn(I) -> T,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+    options: RunOptions,
+) -> PartResult {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let show_progress = options.format.is_text();
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let (outcome, duration, samples, stats) =
+        run_timed(func, input, options.is_timed, show_progress, |output| {
+            let outcome = output.into_answer();
+            if show_progress {
+                print_result(as_deref(&outcome), &part_str, "");
+            }
+            outcome
+        });
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    let (answer, error) = match outcome {
+        Ok(answer) => (answer, None),
+        Err(error) => (None, Some(error)),
+    };
+
+    let result = PartResult {
+        puzzle,
+        part,
+        answer,
+        error,
+        duration,
+        samples,
+        stats,
+    };
+
+    emit_part(&result, options.format);
+
+    if let Some(violation) = result
+        .answer
+        .as_deref()
+        .filter(|_| is_puzzle_input())
+        .and_then(|answer| {
+            Answers::read_from_file(puzzle.year).bound_violation(puzzle.day, part, answer)
+        })
+    {
+        eprintln!("Warning: {violation}.");
+    }
+
+    result
+}
+
+/// Run and print the parse step of a solution.
+/// It is timed once, apart from the parts that share its output.
+pub fn run_parse<'a, P>(
+    func: impl Fn(&'a str) -> P,
+    input: &'a str,
+    puzzle: Puzzle,
+    options: RunOptions,
+) -> (P, PartResult) {
+    let show_progress = options.format.is_text();
+
+    let (parsed, duration, samples, stats) =
+        run_timed(func, input, options.is_timed, show_progress, |parsed| {
+            if show_progress {
+                print!("Parse:");
+            }
+            parsed
+        });
+
+    let result = PartResult {
+        puzzle,
+        part: PARSE_STEP,
+        answer: None,
+        error: None,
+        duration,
+        samples,
+        stats,
+    };
+
+    emit_part(&result, options.format);
+
+    (parsed, result)
+}
+
+/// Print the result of a part in the given format.
+/// Results in the [`OutputFormat::Json`] format are collected and printed with [`output::print_document`].
+pub fn emit_part(result: &PartResult, format: OutputFormat) {
+    match format {
+        OutputFormat::Text if result.part == PARSE_STEP => {
+            print!("\r");
+            println!("Parse:{}", format_duration(result));
+        }
+        OutputFormat::Text => {
+            let badge = result
+                .answer
+                .as_deref()
+                .filter(|_| is_puzzle_input())
+                .map_or("", |answer| {
+                    answer_badge(
+                        &Answers::read_from_file(result.puzzle.year),
+                        result.puzzle.day,
+                        result.part,
+                        answer,
+                    )
+                });
+
+            print_result(
+                result.outcome(),
+                &format!("Part {}", result.part),
+                &format!("{badge}{}", format_duration(result)),
+            );
+        }
+        OutputFormat::JsonLines => println!("{}", output::to_json_line(result)),
+        OutputFormat::Json => {}
+    }
+}
+
+/// Whether this process runs on the puzzle input. Answers to other inputs are not compared with the answer ledger.
+fn is_puzzle_input() -> bool {
+    InputSource::selected().is_puzzle()
+}
+
+/// Print a status message, keeping machine-readable output on stdout clean.
+fn print_status(message: &str, format: OutputFormat) {
+    if format.is_text() {
+        println!("{message}");
+    } else {
+        eprintln!("{message}");
+    }
+}
+
+/// Marks a result that matches (✔) or contradicts (✖) what we know from earlier submissions.
+fn answer_badge(answers: &Answers, day: Day, part: u8, answer: &str) -> &'static str {
+    match answers.correct(day, part) {
+        Some(correct) if correct == answer => " ✔",
+        Some(_) => " ✖",
+        None if answers.verdict(day, part, answer).is_some_and(is_wrong) => " ✖",
+        None => "",
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
+///
+/// The `hook` turns the result of the first execution into the returned value before any benching.
+fn run_timed<I: Clone, T, R>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    is_timed: bool,
+    show_progress: bool,
+    hook: impl FnOnce(T) -> R,
+) -> (R, Duration, u128, Option<BenchStats>) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -41,26 +287,37 @@ fn run_timed<I: Clone, T>(
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let result = hook(result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
+    if !is_timed {
+        return (result, base_time, 1, None);
+    }
 
-    (result, run.0, run.1)
+    let (samples, stats) = bench(func, input, &base_time, show_progress);
+    (result, stats.mean, samples, Some(stats))
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Runs a few warmup iterations before sampling, so caches and branch predictors are primed.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    show_progress: bool,
+) -> (u128, BenchStats) {
+    if show_progress {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    let warmup_iterations = cmp::max(bench_iterations / 10, 3);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -71,35 +328,43 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    (bench_iterations, BenchStats::from_samples(&timers).unwrap())
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(result: &PartResult) -> String {
+    let (duration, samples) = (result.duration, result.samples);
+
+    match &result.stats {
+        Some(stats) => {
+            let outliers = match stats.outliers {
+                0 => String::new(),
+                1 => ", 1 outlier".into(),
+                n => format!(", {n} outliers"),
+            };
+
+            format!(
+                " (median {:.1?} ± {:.1?}, mean {duration:.1?} ± {:.1?}, range {:.1?}..{:.1?} @ {samples} samples{outliers})",
+                stats.median, stats.std_dev, stats.ci_95, stats.min, stats.max
+            )
+        }
+        None if samples == 1 => format!(" ({duration:.1?})"),
+        None => format!(" ({duration:.1?} @ {samples} samples)"),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn as_deref(outcome: &Result<Option<String>, String>) -> Result<Option<&str>, &str> {
+    match outcome {
+        Ok(answer) => Ok(answer.as_deref()),
+        Err(error) => Err(error),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(outcome: Result<Option<&str>, &str>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+    match outcome {
+        Ok(Some(result)) => {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -118,12 +383,24 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                print!("{part}: {ANSI_ITALIC}not implemented{ANSI_RESET}");
             } else {
                 print!("\r");
-                println!("{part}: ✖             ");
+                println!("{part}: {ANSI_ITALIC}not implemented{ANSI_RESET}             ");
+            }
+        }
+        Err(error) => {
+            let str = format!("{part}: {ANSI_RED}✖ failed{ANSI_RESET}{duration_str}");
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}             ");
+                for line in error.lines() {
+                    println!("  {ANSI_RED}{line}{ANSI_RESET}");
+                }
             }
         }
     }
@@ -131,12 +408,15 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
+///  2. a session cookie is available.
+///  3. the result has not been rejected before.
+fn submit_result(
+    result: &str,
+    puzzle: Puzzle,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    answers: &Answers,
+    format: OutputFormat,
+) -> Option<Result<aoc_client::Submission, aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +439,71 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if !is_puzzle_input() {
+        eprintln!("Not submitting {result}, it was not computed from the puzzle input.");
+        return None;
+    }
+
+    if let Some(verdict) = answers
+        .verdict(puzzle.day, part, result)
+        .filter(|v| is_wrong(*v))
+    {
+        eprintln!("Not submitting {result}, it was already rejected as {verdict}.");
+        return None;
+    }
+
+    if let Err(e) = aoc_client::check() {
+        eprintln!("Cannot submit result: {e}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    print_status("Submitting result...", format);
+    Some(aoc_client::submit(puzzle, part, result))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::error::Error;
+    use std::fmt::Display;
+
+    use super::PartOutput;
+
+    #[derive(Debug)]
+    struct InvalidInput(crate::parse::ParseError);
+
+    impl Error for InvalidInput {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    impl Display for InvalidInput {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("invalid input")
+        }
+    }
+
+    #[test]
+    fn tells_missing_answers_from_failures() {
+        assert_eq!(Some(42).into_answer(), Ok(Some("42".into())));
+        assert_eq!(None::<u32>.into_answer(), Ok(None));
+        assert_eq!(Ok::<_, String>(42).into_answer(), Ok(Some("42".into())));
+        assert_eq!(
+            Err::<u32, _>("no path to the exit").into_answer(),
+            Err("no path to the exit".into())
+        );
+    }
+
+    #[test]
+    fn reports_error_causes() {
+        let error = || InvalidInput(crate::parse::ParseError::new(3, "unexpected `x`").at_line(2));
+        let caused = "invalid input\ncaused by: line 2, column 3: unexpected `x`";
+        assert_eq!(Err::<u32, _>(error()).into_answer(), Err(caused.into()));
+        assert_eq!(
+            Err::<u32, Box<dyn Error>>(error().into()).into_answer(),
+            Err(caused.into())
+        );
+    }
 }
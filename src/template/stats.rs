@@ -0,0 +1,179 @@
+/// Summary statistics for the samples of a benchmark run.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Statistics of a benchmarked part.
+/// Samples outside of the Tukey fences (1.5 × IQR beyond the quartiles) are counted as outliers
+/// and left out of `mean`, `std_dev` and `ci_95`. `median`, `min` and `max` are taken over all samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchStats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    /// Half-width of the 95% confidence interval of the mean.
+    pub ci_95: Duration,
+    pub outliers: usize,
+}
+
+impl BenchStats {
+    /// Computes statistics for a set of samples. Returns `None` if there are no samples.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let min = *nanos.first()?;
+        let max = *nanos.last()?;
+
+        let q1 = quantile(&nanos, 0.25);
+        let q3 = quantile(&nanos, 0.75);
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        let kept: Vec<f64> = nanos
+            .iter()
+            .copied()
+            .filter(|x| (lower..=upper).contains(x))
+            .collect();
+
+        let n = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / n;
+        let std_dev = if kept.len() > 1 {
+            (kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            mean: from_nanos(mean),
+            median: from_nanos(quantile(&nanos, 0.5)),
+            min: from_nanos(min),
+            max: from_nanos(max),
+            std_dev: from_nanos(std_dev),
+            ci_95: from_nanos(1.96 * std_dev / n.sqrt()),
+            outliers: nanos.len() - kept.len(),
+        })
+    }
+}
+
+/// Linearly interpolated quantile of sorted, non-empty values.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let mut insert = |key: &str, duration: Duration| {
+            map.insert(key.into(), JsonValue::Number(duration.as_nanos() as f64));
+        };
+
+        insert("mean_nanos", value.mean);
+        insert("median_nanos", value.median);
+        insert("min_nanos", value.min);
+        insert("max_nanos", value.max);
+        insert("std_dev_nanos", value.std_dev);
+        insert("ci_95_nanos", value.ci_95);
+
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        let duration = |key: &str| number(key).map(from_nanos);
+
+        Ok(BenchStats {
+            mean: duration("mean_nanos")?,
+            median: duration("median_nanos")?,
+            min: duration("min_nanos")?,
+            max: duration("max_nanos")?,
+            std_dev: duration("std_dev_nanos")?,
+            ci_95: duration("ci_95_nanos")?,
+            outliers: number("outliers")? as usize,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    use super::BenchStats;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 20, 30, 40, 50])).unwrap();
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(50));
+        assert_eq!(stats.std_dev, Duration::from_nanos(16));
+        assert_eq!(stats.ci_95, Duration::from_nanos(14));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 500])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.mean, Duration::from_nanos(11));
+        assert_eq!(stats.median, Duration::from_nanos(11));
+        assert_eq!(stats.max, Duration::from_nanos(500));
+    }
+
+    #[test]
+    fn handles_single_samples() {
+        let stats = BenchStats::from_samples(&nanos(&[42])).unwrap();
+        assert_eq!(stats.median, Duration::from_nanos(42));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+        assert!(BenchStats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn roundtrips_stats() {
+        let stats = BenchStats::from_samples(&nanos(&[100, 200, 300, 400])).unwrap();
+        let json = JsonValue::from(&stats);
+        assert_eq!(BenchStats::try_from(&json).unwrap(), stats);
+    }
+}
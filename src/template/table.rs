@@ -0,0 +1,32 @@
+/// Prints aligned plain-text tables for terminal output.
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// Print rows as columns padded to the widest cell, with a bold header row.
+pub fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let header: Vec<String> = header.iter().map(|h| (*h).to_string()).collect();
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            std::iter::once(&header)
+                .chain(rows)
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    for (i, row) in std::iter::once(&header).chain(rows).enumerate() {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join(" | ");
+
+        if i == 0 {
+            println!("{ANSI_BOLD}{line}{ANSI_RESET}");
+        } else {
+            println!("{line}");
+        }
+    }
+}
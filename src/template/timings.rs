@@ -1,9 +1,11 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::runner::PARSE_STEP;
+use crate::template::stats::BenchStats;
+use crate::template::{workspace_path, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,27 +13,97 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    /// The errors of parts that failed. Failed parts are not timed.
+    pub part_1_error: Option<String>,
+    pub part_2_error: Option<String>,
+    /// Only set for solutions that parse their input in a separate step.
+    pub parse: Option<String>,
+    pub parse_stats: Option<BenchStats>,
     pub total_nanos: f64,
 }
 
-/// Represents benchmark times for a set of days.
+impl Timing {
+    /// A day without any timed parts.
+    pub fn new(day: Day) -> Self {
+        Self {
+            day,
+            part_1: None,
+            part_2: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_error: None,
+            part_2_error: None,
+            parse: None,
+            parse_stats: None,
+            total_nanos: 0_f64,
+        }
+    }
+
+    /// The time of a part or the parse step: its median if it was benchmarked, its stored duration otherwise.
+    pub fn part_time(&self, part: u8) -> Option<Duration> {
+        let (duration, stats) = match part {
+            PARSE_STEP => (&self.parse, &self.parse_stats),
+            1 => (&self.part_1, &self.part_1_stats),
+            2 => (&self.part_2, &self.part_2_stats),
+            _ => return None,
+        };
+
+        match stats {
+            Some(stats) => Some(stats.median),
+            None => parse_duration(duration.as_deref()?),
+        }
+    }
+}
+
+/// Parses a duration formatted with `{:.1?}`, e.g. `74.1ms`.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let (value, unit_nanos) = [("ns", 1.0), ("µs", 1e3), ("ms", 1e6), ("s", 1e9)]
+        .into_iter()
+        .find_map(|(unit, nanos)| Some((s.strip_suffix(unit)?, nanos)))?;
+
+    let nanos = value.parse::<f64>().ok()? * unit_nanos;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some(Duration::from_nanos(nanos.round() as u64))
+}
+
+/// Represents a single benchmark run of a day in the timing history.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    pub day: Day,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The commit `HEAD` pointed to during the run, if the run happened in a git repository.
+    pub commit: Option<String>,
+    pub parse_nanos: Option<f64>,
+    pub part_1_nanos: Option<f64>,
+    pub part_2_nanos: Option<f64>,
+}
+
+/// Represents benchmark times for a set of days of one year, stored at `data/{year}/timings.json`.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    /// Every recorded benchmark run, oldest first. Entries are only ever appended.
+    pub history: Vec<HistoryEntry>,
 }
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let data_dir = workspace_path(year.data_dir());
+        fs::create_dir_all(&data_dir)?;
+        let mut file = fs::File::create(data_dir.join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(workspace_path(year.data_dir()).join(TIMINGS_FILE_NAME))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -51,8 +123,32 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|a| a.day);
+
+        let history = self.history.iter().chain(&new.history).cloned().collect();
+
+        Timings { data, history }
+    }
+
+    /// Append the days of a benchmark run to the history.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn record_history(&mut self, run: &Timings, timestamp: u64, commit: Option<&str>) {
+        let nanos = |timing: &Timing, part| timing.part_time(part).map(|d| d.as_nanos() as f64);
+
+        self.history
+            .extend(run.data.iter().map(|timing| HistoryEntry {
+                day: timing.day,
+                timestamp,
+                commit: commit.map(String::from),
+                parse_nanos: nanos(timing, PARSE_STEP),
+                part_1_nanos: nanos(timing, 1),
+                part_2_nanos: nanos(timing, 2),
+            }));
+    }
+
+    /// The recorded benchmark runs of a day, oldest first.
+    pub fn day_history(&self, day: Day) -> impl Iterator<Item = &HistoryEntry> {
+        self.history.iter().filter(move |entry| entry.day == day)
     }
 
     /// Sum up total duration of timings as millis.
@@ -60,10 +156,12 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+    /// Whether every part of a day of `year` is benched.
+    /// The last day of an event has no second part to solve, so its first part is enough.
+    pub fn is_day_complete(&self, year: Year, day: Day) -> bool {
+        self.data.iter().any(|t| {
+            t.day == day && t.part_1.is_some() && (t.part_2.is_some() || day == year.last_day())
+        })
     }
 }
 
@@ -78,6 +176,11 @@ impl From<Timings> for JsonValue {
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -88,19 +191,34 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        // timings stored before the history was recorded have no `history` key.
+        let json_history = match json.get("history") {
+            Some(history) => history
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?
+                .as_slice(),
+            None => &[],
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            history: json_history
+                .iter()
+                .map(HistoryEntry::try_from)
+                .collect::<Result<_, _>>()?,
         })
     }
 }
@@ -133,6 +251,35 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, error) in [
+            ("part_1_error", &value.part_1_error),
+            ("part_2_error", &value.part_2_error),
+        ] {
+            map.insert(
+                key.into(),
+                error.clone().map_or(JsonValue::Null, JsonValue::String),
+            );
+        }
+
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        for (key, stats) in [
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+            ("parse_stats", &value.parse_stats),
+        ] {
+            map.insert(
+                key.into(),
+                stats.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,10 +313,40 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // timings stored before stats and parse steps were recorded have no keys for them.
+        let stats = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => BenchStats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
+        // timings stored before failures were recorded have no keys for them.
+        let error = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected timing.{key} to be null or string.")),
+            _ => Ok(None),
+        };
+
+        let parse = match json.get("parse") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be null or string.")?,
+            ),
+            _ => None,
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats: stats("part_1_stats")?,
+            part_2_stats: stats("part_2_stats")?,
+            part_1_error: error("part_1_error")?,
+            part_2_error: error("part_2_error")?,
+            parse: parse.cloned(),
+            parse_stats: stats("parse_stats")?,
             total_nanos,
         })
     }
@@ -177,6 +354,84 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&HistoryEntry> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        for (key, nanos) in [
+            ("parse_nanos", value.parse_nanos),
+            ("part_1_nanos", value.part_1_nanos),
+            ("part_2_nanos", value.part_2_nanos),
+        ] {
+            map.insert(key.into(), nanos.map_or(JsonValue::Null, JsonValue::Number));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected history.day to be a Day struct.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected history.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected history.commit to be null or string.")?;
+
+        let nanos = |key: &str| {
+            json.get(key)
+                .map(|v| if v.is_null() { None } else { v.get::<f64>() })
+                .ok_or(format!("Expected history.{key} to be null or number."))
+        };
+
+        Ok(HistoryEntry {
+            day,
+            timestamp: *timestamp as u64,
+            commit: commit.cloned(),
+            // runs recorded before parse steps were timed have no `parse_nanos`.
+            parse_nanos: json
+                .get("parse_nanos")
+                .and_then(|v| v.get::<f64>())
+                .copied(),
+            part_1_nanos: nanos("part_1_nanos")?.copied(),
+            part_2_nanos: nanos("part_2_nanos")?.copied(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
@@ -187,24 +442,24 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
-                    part_2: None,
                     total_nanos: 4e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
+            history: vec![],
         }
     }
 
@@ -223,6 +478,15 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_failed_parts() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "part_2_error": "unexpected `x`", "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_error, None);
+            assert_eq!(timing.part_2_error, Some("unexpected `x`".to_string()));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -272,48 +536,175 @@ mod tests {
         use crate::{
             day,
             template::timings::{Timing, Timings},
+            year,
         };
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(year!(2024), day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
-                    part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(year!(2024), day!(1)), false);
+        }
+
+        #[test]
+        fn handles_last_days() {
+            let timings = Timings {
+                data: vec![Timing {
+                    part_1: Some("1ms".into()),
+                    total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(day!(12))
+                }],
+                history: vec![],
+            };
+
+            assert_eq!(timings.is_day_complete(year!(2025), day!(12)), true);
+            assert_eq!(timings.is_day_complete(year!(2024), day!(12)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
+                    ..Timing::new(day!(1))
+                }],
+                history: vec![],
+            };
+
+            assert_eq!(timings.is_day_complete(year!(2024), day!(1)), false);
+        }
+    }
+
+    mod history {
+        use crate::{
+            day,
+            template::timings::{HistoryEntry, Timing, Timings},
+        };
+
+        use super::get_mock_timings;
+
+        fn get_run() -> Timings {
+            Timings {
+                data: vec![Timing {
+                    part_1: Some("1.5ms".into()),
+                    total_nanos: 1_500_000_f64,
+                    ..Timing::new(day!(2))
                 }],
+                history: vec![],
+            }
+        }
+
+        #[test]
+        fn records_runs() {
+            let mut timings = get_mock_timings();
+            timings.record_history(&get_run(), 1_700_000_000, Some("abc"));
+            timings.record_history(&get_run(), 1_700_000_100, None);
+
+            let history: Vec<_> = timings.day_history(day!(2)).collect();
+            assert_eq!(history.len(), 2);
+            assert_eq!(history[0].commit, Some("abc".into()));
+            assert_eq!(history[0].part_1_nanos, Some(1_500_000_f64));
+            assert_eq!(history[0].part_2_nanos, None);
+            assert_eq!(history[1].timestamp, 1_700_000_100);
+            assert_eq!(timings.day_history(day!(1)).count(), 0);
+        }
+
+        #[test]
+        fn roundtrips_history() {
+            let mut timings = get_mock_timings();
+            timings.record_history(&get_run(), 1_700_000_000, Some("abc"));
+
+            let json = tinyjson::JsonValue::from(timings).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.history.len(), 1);
+            assert_eq!(timings.history[0].day, day!(2));
+            assert_eq!(timings.history[0].timestamp, 1_700_000_000);
+        }
+
+        #[test]
+        fn roundtrips_parse_times() {
+            let entry = HistoryEntry {
+                day: day!(5),
+                timestamp: 1_700_000_000,
+                commit: None,
+                parse_nanos: Some(2_500_f64),
+                part_1_nanos: Some(1_000_f64),
+                part_2_nanos: None,
+            };
+
+            let json = tinyjson::JsonValue::from(&entry);
+            let parsed = HistoryEntry::try_from(&json).unwrap();
+            assert_eq!(parsed.parse_nanos, Some(2_500_f64));
+            assert_eq!(parsed.part_1_nanos, Some(1_000_f64));
+            assert_eq!(parsed.part_2_nanos, None);
+        }
+
+        #[test]
+        fn merges_history() {
+            let mut timings = get_mock_timings();
+            timings.record_history(&get_run(), 1, None);
+            let mut other = get_run();
+            other.record_history(&get_run(), 2, None);
+
+            let merged = timings.merge(&other);
+            assert_eq!(merged.history.len(), 2);
+            assert_eq!(merged.history[0].timestamp, 1);
+            assert_eq!(merged.history[1].timestamp, 2);
+        }
+    }
+
+    mod part_time {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{stats::BenchStats, timings::Timing},
+        };
+
+        #[test]
+        fn parses_stored_durations() {
+            let timing = Timing {
+                part_1: Some("74.1ms".into()),
+                part_2: Some("2.5µs".into()),
+                ..Timing::new(day!(1))
+            };
+
+            assert_eq!(timing.part_time(1), Some(Duration::from_micros(74_100)));
+            assert_eq!(timing.part_time(2), Some(Duration::from_nanos(2_500)));
+            assert_eq!(timing.part_time(3), None);
+        }
+
+        #[test]
+        fn prefers_median() {
+            let timing = Timing {
+                part_1: Some("1.0s".into()),
+                part_1_stats: BenchStats::from_samples(&[Duration::from_millis(900)]),
+                ..Timing::new(day!(1))
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timing.part_time(1), Some(Duration::from_millis(900)));
+            assert_eq!(timing.part_time(2), None);
         }
     }
 
@@ -330,11 +721,9 @@ mod tests {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Timing::new(day!(3))
                 }],
+                history: vec![],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -350,11 +739,9 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Timing::new(day!(2))
                 }],
+                history: vec![],
             };
             let merged = timings.merge(&other);
 
@@ -0,0 +1,170 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::template::Day;
+
+/// The year of the first Advent of Code event.
+const FIRST_YEAR: u16 = 2015;
+
+/// The year of the first event that is shortened to 12 days.
+const FIRST_SHORT_YEAR: u16 = 2025;
+
+/// A valid year of Advent of Code (i.e. 2015 or later).
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if Advent of Code took place in it,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Parses the `AOC_YEAR` a solution was compiled with, see [`solution!`](crate::solution).
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __from_compile_env(year: Option<&str>) -> Self {
+        let Some(year) = year else {
+            panic!("`AOC_YEAR` is not set, pass the year to `solution!` with `year = ...`.");
+        };
+
+        let bytes = year.as_bytes();
+        let mut value: u16 = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            assert!(
+                bytes[i].is_ascii_digit() && bytes.len() == 4,
+                "`AOC_YEAR` is not a valid year."
+            );
+            value = value * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        assert!(value >= FIRST_YEAR, "`AOC_YEAR` is not a valid year.");
+        Self(value)
+    }
+
+    /// The year configured with the `AOC_YEAR` environment variable, which `.cargo/config.toml` sets.
+    /// Outside of cargo, e.g. when a binary is run directly, the `AOC_YEAR` it was compiled with is used.
+    /// Commands use it unless they are given a `--year`.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR")
+            .ok()
+            .or_else(|| option_env!("AOC_YEAR").map(String::from))?
+            .parse()
+            .ok()
+    }
+
+    /// The number of days in this year's event: 25 until 2024, 12 since.
+    /// Set `AOC_DAYS_{year}` (e.g. `AOC_DAYS_2026 = "25"`) to a number between 1 and 25 to override it for a year.
+    pub fn day_count(self) -> u8 {
+        env::var(format!("AOC_DAYS_{}", self.0))
+            .ok()
+            .and_then(|count| count.parse().ok())
+            .filter(|count| (1..=25).contains(count))
+            .unwrap_or(if self.0 < FIRST_SHORT_YEAR { 25 } else { 12 })
+    }
+
+    /// The last day of this year's event. Its second part is a free star, so it only has one part to solve.
+    pub fn last_day(self) -> Day {
+        // NOTE: the day count of a year is always between 1 and 25.
+        Day::__new_unchecked(self.day_count())
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The folder holding the inputs, examples, puzzles, answers and timings of this year.
+    pub fn data_dir(self) -> PathBuf {
+        PathBuf::from("data").join(self.to_string())
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!("invalid year `", $year, "`, expecting 2015 or later"),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2024".parse::<Year>().unwrap(), Year(2024));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("24".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn counts_event_days() {
+        assert_eq!(Year(2015).day_count(), 25);
+        assert_eq!(Year(2024).day_count(), 25);
+        assert_eq!(Year(2025).last_day(), crate::day!(12));
+    }
+
+    #[test]
+    fn parses_compile_env() {
+        assert_eq!(Year::__from_compile_env(Some("2023")), Year(2023));
+    }
+}